@@ -0,0 +1,41 @@
+use crate::ComputationStatus;
+use json::JsonValue;
+
+impl ComputationStatus {
+    /// Convert this status to JSON (`running`, `cancelled`, `elapsed`, `progress`,
+    /// `resultsFound` and the computation-specific `details`).
+    pub fn to_json(&self) -> JsonValue {
+        object! {
+            "running" => self.running,
+            "cancelled" => self.cancelled,
+            "elapsed" => self.elapsed,
+            "progress" => self.progress,
+            "resultsFound" => self.results_found,
+            "details" => self.details.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ComputationStatus;
+
+    #[test]
+    fn computation_status_json() {
+        let status = ComputationStatus {
+            running: true,
+            cancelled: false,
+            elapsed: 1200,
+            progress: 0.5,
+            results_found: 3,
+            details: object! { "sizesProcessed" => 2 },
+        };
+        let json = status.to_json();
+        assert_eq!(json["running"], true);
+        assert_eq!(json["cancelled"], false);
+        assert_eq!(json["elapsed"].as_u64(), Some(1200));
+        assert_eq!(json["progress"].as_f64(), Some(0.5));
+        assert_eq!(json["resultsFound"].as_usize(), Some(3));
+        assert_eq!(json["details"]["sizesProcessed"].as_usize(), Some(2));
+    }
+}
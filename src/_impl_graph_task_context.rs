@@ -41,6 +41,16 @@ impl GraphTaskContext {
         self.progress.increment_result_count();
     }
 
+    /// Number of results reported through `increment_result_count`.
+    pub fn get_result_count(&self) -> usize {
+        self.progress.get_result_count()
+    }
+
+    /// Fraction of the state space that is already processed.
+    pub fn get_progress_fraction(&self) -> f64 {
+        self.progress.get_progress_fraction()
+    }
+
     /// Output a string that represents the percentage of remaining state space.
     pub fn get_progress_string(&self) -> String {
         self.progress.get_progress_string()
@@ -1,4 +1,4 @@
-use crate::bdt::{Attribute, AttributeContext, Bdt, BifurcationFunction};
+use crate::bdt::{Attribute, AttributeContext, AttributeKind, Bdt, BifurcationFunction};
 use crate::util::functional::Functional;
 use biodivine_lib_bdd::Bdd;
 use biodivine_lib_param_bn::biodivine_std::traits::Set;
@@ -28,7 +28,7 @@ impl Bdt {
                 is_not_empty && !a.negative.intersect(graph.unit_colors()).is_empty()
             })
             .collect();
-        Bdt::new(classes, attributes)
+        Bdt::new(classes, attributes, network)
     }
 }
 
@@ -48,6 +48,7 @@ fn attributes_for_network_inputs(
                 .mk_implicit_function_is_true(v, &[]);
             out.push(Attribute {
                 name: network.get_variable_name(v).clone(),
+                kind: AttributeKind::Input { variable: v },
                 negative: graph.empty_colors().copy(bdd.not()),
                 positive: graph.empty_colors().copy(bdd),
                 context: None,
@@ -70,6 +71,7 @@ fn attributes_for_constant_parameters(
                 .mk_uninterpreted_function_is_true(p, &[]);
             out.push(Attribute {
                 name: network[p].get_name().clone(),
+                kind: AttributeKind::ConstantParameter { parameter: p },
                 negative: graph.empty_colors().copy(bdd.not()),
                 positive: graph.empty_colors().copy(bdd),
                 context: None,
@@ -115,6 +117,10 @@ fn attributes_for_missing_constraints(
                     network.get_variable_name(reg.get_regulator()),
                     network.get_variable_name(reg.get_target()),
                 ),
+                kind: AttributeKind::Essentiality {
+                    regulator: reg.get_regulator(),
+                    target: reg.get_target(),
+                },
                 negative: graph.empty_colors().copy(observability.not()),
                 positive: graph.empty_colors().copy(observability),
                 context: Some(AttributeContext {
@@ -144,6 +150,10 @@ fn attributes_for_missing_constraints(
                     network.get_variable_name(reg.get_regulator()),
                     network.get_variable_name(reg.get_target()),
                 ),
+                kind: AttributeKind::Activation {
+                    regulator: reg.get_regulator(),
+                    target: reg.get_target(),
+                },
                 positive: graph.empty_colors().copy(non_activation.not()),
                 negative: graph.empty_colors().copy(non_activation),
                 context: Some(AttributeContext {
@@ -159,6 +169,10 @@ fn attributes_for_missing_constraints(
                     network.get_variable_name(reg.get_regulator()),
                     network.get_variable_name(reg.get_target()),
                 ),
+                kind: AttributeKind::Inhibition {
+                    regulator: reg.get_regulator(),
+                    target: reg.get_target(),
+                },
                 positive: graph.empty_colors().copy(non_inhibition.not()),
                 negative: graph.empty_colors().copy(non_inhibition),
                 context: Some(AttributeContext {
@@ -188,6 +202,11 @@ fn attributes_for_implicit_function_tables(
                 .unwrap();
             for (ctx, var) in table {
                 let bdd = graph.symbolic_context().bdd_variable_set().mk_var(var);
+                let valuation: Vec<(VariableId, bool)> = network
+                    .regulators(v)
+                    .into_iter()
+                    .zip(ctx.iter().cloned())
+                    .collect();
                 let ctx: Vec<String> = ctx
                     .into_iter()
                     .zip(network.regulators(v))
@@ -202,6 +221,10 @@ fn attributes_for_implicit_function_tables(
                 let name = format!("{}{:?}", network.get_variable_name(v), ctx);
                 out.push(Attribute {
                     name: name.replace("\"", ""),
+                    kind: AttributeKind::ImplicitTableRow {
+                        target: v,
+                        valuation,
+                    },
                     negative: graph.mk_empty_colors().copy(bdd.not()),
                     positive: graph.mk_empty_colors().copy(bdd),
                     context: None,
@@ -226,6 +249,7 @@ fn attributes_for_explicit_function_tables(
                 .collect::<Vec<_>>();
             for (ctx, var) in table {
                 let bdd = graph.symbolic_context().bdd_variable_set().mk_var(var);
+                let valuation = ctx.clone();
                 let ctx: Vec<String> = ctx
                     .into_iter()
                     .zip(&arg_names)
@@ -234,6 +258,10 @@ fn attributes_for_explicit_function_tables(
                 let name = format!("{}{:?}", parameter.get_name(), ctx);
                 out.push(Attribute {
                     name: name.replace("\"", ""),
+                    kind: AttributeKind::ExplicitTableRow {
+                        parameter: p,
+                        valuation,
+                    },
                     negative: graph.mk_empty_colors().copy(bdd.not()),
                     positive: graph.mk_empty_colors().copy(bdd),
                     context: None,
@@ -280,9 +308,13 @@ fn attributes_for_conditional_observability(
                         let bdd_1 = context.mk_state_variable_is_true(*c_var);
                         let bdd_0 = bdd_1.not();
                         let name = network.get_variable_name(*c_var);
-                        [(format!("¬{}", name), bdd_0), (name.clone(), bdd_1)].to_vec()
+                        [
+                            (format!("¬{}", name), (*c_var, false), bdd_0),
+                            (name.clone(), (*c_var, true), bdd_1),
+                        ]
+                        .to_vec()
                     })
-                    .collect::<Vec<(String, Bdd)>>();
+                    .collect::<Vec<Condition>>();
                 // All non-empty combinations of conditions
                 let contexts = make_contexts(&conditions);
 
@@ -292,7 +324,8 @@ fn attributes_for_conditional_observability(
                 let regulator_is_false = context.mk_state_variable_is_true(r).not();
 
                 // Unconditional observability is already covered above, so we don't handle it here
-                for (condition_name, condition_list, condition_bdd) in contexts {
+                for (condition_name, condition_list, condition_literals, condition_bdd) in contexts
+                {
                     // Restrict to values that satisfy conditions
                     let fn_is_true = fn_is_true.and(&condition_bdd);
                     let fn_x1_to_1 = fn_is_true.and(&regulator_is_true).var_exists(r_var);
@@ -308,6 +341,11 @@ fn attributes_for_conditional_observability(
                             network.get_variable_name(v),
                             condition_name,
                         ),
+                        kind: AttributeKind::ConditionalEssentiality {
+                            regulator: r,
+                            target: v,
+                            condition: condition_literals,
+                        },
                         negative: graph.empty_colors().copy(observability.not()),
                         positive: graph.empty_colors().copy(observability),
                         context: Some(AttributeContext {
@@ -345,6 +383,14 @@ fn variable_contexts(function: &FnUpdate) -> Vec<Vec<VariableId>> {
     }
 }
 
+/// **(internal)** A labeled condition: its name, the variable literal it represents,
+/// and its symbolic representation.
+type Condition = (String, (VariableId, bool), Bdd);
+
+/// **(internal)** A combination of conditions: the full name, names of individual conditions,
+/// the variable literals, and the symbolic representation of the whole combination.
+type ConditionCombination = (String, Vec<String>, Vec<(VariableId, bool)>, Bdd);
+
 /// Build all combinations of labeled conditions.
 ///
 /// For example, given X, Y, Z, this will produce:
@@ -354,23 +400,29 @@ fn variable_contexts(function: &FnUpdate) -> Vec<Vec<VariableId>> {
 ///
 /// This should also automatically filter out empty results, so you can
 /// include `A` and `!A` in the conditions without problems.
-fn make_contexts(conditions: &[(String, Bdd)]) -> Vec<(String, Vec<String>, Bdd)> {
+fn make_contexts(conditions: &[Condition]) -> Vec<ConditionCombination> {
     fn recursion(
-        conditions: &[(String, Bdd)],
-        partial_condition: &(String, Vec<String>, Bdd),
-        out: &mut Vec<(String, Vec<String>, Bdd)>,
+        conditions: &[Condition],
+        partial_condition: &ConditionCombination,
+        out: &mut Vec<ConditionCombination>,
     ) {
         if conditions.is_empty() {
             return;
         }
         for (i, c) in conditions.iter().enumerate() {
             let updated_name = format!("{}, {}", partial_condition.0, c.0);
-            let updated_colors = partial_condition.2.and(&c.1);
+            let updated_colors = partial_condition.3.and(&c.2);
             if updated_colors.is_false() {
                 continue;
             }
             let update_condition_list = partial_condition.1.clone().apply(|i| i.push(c.0.clone()));
-            let updated = (updated_name, update_condition_list, updated_colors);
+            let update_literal_list = partial_condition.2.clone().apply(|i| i.push(c.1));
+            let updated = (
+                updated_name,
+                update_condition_list,
+                update_literal_list,
+                updated_colors,
+            );
             if i != conditions.len() - 1 {
                 recursion(&conditions[(i + 1)..], &updated, out);
             }
@@ -380,12 +432,12 @@ fn make_contexts(conditions: &[(String, Bdd)]) -> Vec<(String, Vec<String>, Bdd)
     if conditions.is_empty() {
         return vec![];
     }
-    let mut result: Vec<(String, Vec<String>, Bdd)> = Vec::new();
+    let mut result: Vec<ConditionCombination> = Vec::new();
     for (i, c) in conditions.iter().enumerate() {
-        if c.1.is_false() {
+        if c.2.is_false() {
             continue;
         }
-        let pair = (c.0.clone(), vec![c.0.clone()], c.1.clone());
+        let pair = (c.0.clone(), vec![c.0.clone()], vec![c.1], c.2.clone());
         if i != conditions.len() - 1 {
             recursion(&conditions[(i + 1)..], &pair, &mut result);
         }
@@ -1,10 +1,20 @@
-use crate::bdt::{Attribute, BifurcationFunction};
+use crate::bdt::{Attribute, AttributeKind, BifurcationFunction};
 use crate::util::functional::Functional;
 use biodivine_lib_param_bn::biodivine_std::traits::Set;
 use biodivine_lib_param_bn::symbolic_async_graph::GraphColors;
 use std::collections::HashMap;
 
 impl Attribute {
+    /// Human-readable name of this attribute.
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// The kind of network property represented by this attribute.
+    pub fn kind(&self) -> &AttributeKind {
+        &self.kind
+    }
+
     /// Apply this attribute to the given bifurcation function, splitting it into two.
     pub fn split_function(
         &self,
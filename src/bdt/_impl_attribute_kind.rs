@@ -0,0 +1,122 @@
+use crate::bdt::AttributeKind;
+use crate::util::functional::Functional;
+use biodivine_lib_param_bn::{ParameterId, VariableId};
+use json::JsonValue;
+
+impl AttributeKind {
+    /// A short machine-readable identifier of the attribute kind.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            AttributeKind::Input { .. } => "input",
+            AttributeKind::ConstantParameter { .. } => "constant_parameter",
+            AttributeKind::Essentiality { .. } => "essentiality",
+            AttributeKind::Activation { .. } => "activation",
+            AttributeKind::Inhibition { .. } => "inhibition",
+            AttributeKind::ImplicitTableRow { .. } => "implicit_table_row",
+            AttributeKind::ExplicitTableRow { .. } => "explicit_table_row",
+            AttributeKind::ConditionalEssentiality { .. } => "conditional_essentiality",
+        }
+    }
+
+    /// Convert this kind to a JSON object with the kind type and its structured payload.
+    ///
+    /// Variables and parameters are exported as objects with both an `id` and a `name`.
+    /// The names are resolved using the given name tables (indexed by variable/parameter id).
+    pub fn to_json(&self, variable_names: &[String], parameter_names: &[String]) -> JsonValue {
+        let variable = |id: VariableId| -> JsonValue {
+            object! {
+                "id" => id.to_index(),
+                "name" => variable_names.get(id.to_index()).cloned(),
+            }
+        };
+        let parameter = |id: ParameterId| -> JsonValue {
+            object! {
+                "id" => id.to_index(),
+                "name" => parameter_names.get(id.to_index()).cloned(),
+            }
+        };
+        let valuation = |values: &[(VariableId, bool)]| -> JsonValue {
+            JsonValue::new_array().apply(|array| {
+                for (var, value) in values {
+                    let literal = variable(*var).apply(|it| it.insert("value", *value).unwrap());
+                    array.push(literal).unwrap();
+                }
+            })
+        };
+
+        let mut result = object! { "type" => self.type_name() };
+        match self {
+            AttributeKind::Input { variable: var } => {
+                result.insert("variable", variable(*var)).unwrap();
+            }
+            AttributeKind::ConstantParameter { parameter: param } => {
+                result.insert("parameter", parameter(*param)).unwrap();
+            }
+            AttributeKind::Essentiality { regulator, target }
+            | AttributeKind::Activation { regulator, target }
+            | AttributeKind::Inhibition { regulator, target } => {
+                result.insert("regulator", variable(*regulator)).unwrap();
+                result.insert("target", variable(*target)).unwrap();
+            }
+            AttributeKind::ImplicitTableRow {
+                target,
+                valuation: row,
+            } => {
+                result.insert("target", variable(*target)).unwrap();
+                result.insert("valuation", valuation(row)).unwrap();
+            }
+            AttributeKind::ExplicitTableRow {
+                parameter: param,
+                valuation: row,
+            } => {
+                result.insert("parameter", parameter(*param)).unwrap();
+                result.insert("valuation", row.clone()).unwrap();
+            }
+            AttributeKind::ConditionalEssentiality {
+                regulator,
+                target,
+                condition,
+            } => {
+                result.insert("regulator", variable(*regulator)).unwrap();
+                result.insert("target", variable(*target)).unwrap();
+                result.insert("condition", valuation(condition)).unwrap();
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bdt::Bdt;
+    use crate::scc::Class;
+    use biodivine_lib_param_bn::BooleanNetwork;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use std::collections::HashMap;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn attribute_kinds_for_network() {
+        // `A` is an input and `B` has an implicit (monotone, non-observable) update function.
+        let network = BooleanNetwork::try_from("A ->? B\n").unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let classes =
+            HashMap::from([(Class::try_read_string("S").unwrap(), graph.mk_unit_colors())]);
+        let tree = Bdt::new_from_graph(classes, &graph, &network);
+        let kinds = tree
+            .attributes()
+            .map(|id| tree.attribute_kind_json(id))
+            .collect::<Vec<_>>();
+
+        let input = kinds.iter().find(|it| it["type"] == "input").unwrap();
+        assert_eq!(input["variable"]["name"], "A");
+        let row = kinds
+            .iter()
+            .find(|it| it["type"] == "implicit_table_row")
+            .unwrap();
+        assert_eq!(row["target"]["name"], "B");
+        assert_eq!(row["valuation"][0]["name"], "A");
+        assert!(row["valuation"][0]["value"].is_boolean());
+        assert!(kinds.iter().any(|it| it["type"] == "essentiality"));
+    }
+}
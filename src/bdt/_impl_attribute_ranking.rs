@@ -0,0 +1,219 @@
+use crate::bdt::{
+    AttributeRanking, Bdt, BdtNode, BifurcationFunction, ClassRanking, entropy, information_gain,
+};
+use crate::util::functional::Functional;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use json::JsonValue;
+use num_traits::Zero;
+use std::collections::HashMap;
+
+impl Bdt {
+    /// Rank all attributes of this tree by how much they explain the class variation in the
+    /// whole parameter space (i.e. the root node of the tree).
+    ///
+    /// The result is sorted by mutual information (highest first, ties keep the attribute
+    /// order). Attributes that do not split the root parameter space at all are still
+    /// included, but naturally end up last.
+    pub fn attribute_ranking(&self) -> Vec<AttributeRanking> {
+        let classes: BifurcationFunction = match &self[self.root_id()] {
+            BdtNode::Leaf { class, params } => HashMap::from([(class.clone(), params.clone())]),
+            BdtNode::Decision { classes, .. } => classes.clone(),
+            BdtNode::Unprocessed { classes } => classes.clone(),
+        };
+        let total: f64 = classes.values().map(|it| it.approx_cardinality()).sum();
+        let original_entropy = entropy(&classes);
+
+        self.attributes()
+            .map(|id| {
+                let attribute = &self[id];
+                let (negative, positive) = attribute.split_function(&classes);
+                let positive_total: f64 = positive.values().map(|it| it.approx_cardinality()).sum();
+                let negative_total: f64 = negative.values().map(|it| it.approx_cardinality()).sum();
+
+                let mut class_rankings = Vec::new();
+                let mut mutual_information = 0.0;
+                for (class, params) in &classes {
+                    let in_positive = attribute.positive.intersect(params);
+                    let in_negative = attribute.negative.intersect(params);
+                    let class_total = params.approx_cardinality();
+                    let class_positive = in_positive.approx_cardinality();
+                    let class_negative = in_negative.approx_cardinality();
+                    // Contribution of this class to I(Class; Attribute).
+                    mutual_information +=
+                        mutual_information_term(class_positive, class_total, positive_total, total);
+                    mutual_information +=
+                        mutual_information_term(class_negative, class_total, negative_total, total);
+                    class_rankings.push(ClassRanking {
+                        class: class.clone(),
+                        mutual_information: indicator_mutual_information(
+                            class_positive,
+                            class_negative,
+                            positive_total,
+                            negative_total,
+                        ),
+                        positive_cardinality: in_positive.exact_cardinality(),
+                        negative_cardinality: in_negative.exact_cardinality(),
+                    });
+                }
+                class_rankings.sort_by(|a, b| a.class.cmp(&b.class));
+
+                AttributeRanking {
+                    attribute: id,
+                    information_gain: information_gain(
+                        original_entropy,
+                        entropy(&negative),
+                        entropy(&positive),
+                    ),
+                    mutual_information,
+                    positive_cardinality: class_rankings
+                        .iter()
+                        .map(|it| it.positive_cardinality.clone())
+                        .sum(),
+                    negative_cardinality: class_rankings
+                        .iter()
+                        .map(|it| it.negative_cardinality.clone())
+                        .sum(),
+                    classes: class_rankings,
+                }
+            })
+            .collect::<Vec<_>>()
+            .apply(|it| it.sort_by(|l, r| r.mutual_information.total_cmp(&l.mutual_information)))
+    }
+
+    /// Compute the attribute ranking (see `Bdt::attribute_ranking`) as a JSON array.
+    ///
+    /// Exact cardinalities are exported as strings, since they can easily exceed the range
+    /// of JSON numbers.
+    pub fn attribute_ranking_json(&self) -> JsonValue {
+        self.attribute_ranking()
+            .into_iter()
+            .map(|it| {
+                let classes = it
+                    .classes
+                    .iter()
+                    .map(|c| {
+                        object! {
+                            "class" => format!("{}", c.class),
+                            "mutual_information" => c.mutual_information,
+                            "positive_cardinality" => c.positive_cardinality.to_string(),
+                            "negative_cardinality" => c.negative_cardinality.to_string(),
+                            "separated" => c.is_separated(),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                object! {
+                    "id" => it.attribute.to_index(),
+                    "name" => self[it.attribute].name.clone(),
+                    "kind" => self.attribute_kind_json(it.attribute),
+                    "gain" => it.information_gain,
+                    "mutual_information" => it.mutual_information,
+                    "positive_cardinality" => it.positive_cardinality.to_string(),
+                    "negative_cardinality" => it.negative_cardinality.to_string(),
+                    "classes" => classes,
+                }
+            })
+            .collect::<Vec<_>>()
+            .and_then(JsonValue::from)
+    }
+}
+
+impl ClassRanking {
+    /// A class is separated by an attribute if all its colours are on one side of the
+    /// attribute (i.e. the attribute uniquely determines which side the class belongs to).
+    pub fn is_separated(&self) -> bool {
+        self.positive_cardinality.is_zero() || self.negative_cardinality.is_zero()
+    }
+}
+
+/// **(internal)** One term `p(x,y) * log2(p(x,y) / (p(x) * p(y)))` of the mutual
+/// information sum, computed from (approximate) cardinalities.
+fn mutual_information_term(joint: f64, x_total: f64, y_total: f64, total: f64) -> f64 {
+    if joint <= 0.0 || x_total <= 0.0 || y_total <= 0.0 || total <= 0.0 {
+        return 0.0;
+    }
+    let p_joint = joint / total;
+    p_joint * ((joint * total) / (x_total * y_total)).log2()
+}
+
+/// **(internal)** Mutual information between the attribute and the indicator variable of
+/// one class (i.e. "is in class" vs. "is in some other class").
+fn indicator_mutual_information(
+    class_positive: f64,
+    class_negative: f64,
+    positive_total: f64,
+    negative_total: f64,
+) -> f64 {
+    let total = positive_total + negative_total;
+    let class_total = class_positive + class_negative;
+    let other_total = total - class_total;
+    let other_positive = positive_total - class_positive;
+    let other_negative = negative_total - class_negative;
+    mutual_information_term(class_positive, class_total, positive_total, total)
+        + mutual_information_term(class_negative, class_total, negative_total, total)
+        + mutual_information_term(other_positive, other_total, positive_total, total)
+        + mutual_information_term(other_negative, other_total, negative_total, total)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bdt::{Attribute, AttributeKind, Bdt};
+    use crate::scc::Class;
+    use biodivine_lib_param_bn::BooleanNetwork;
+    use biodivine_lib_param_bn::biodivine_std::traits::Set;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use std::collections::HashMap;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn attribute_ranking_order() {
+        let network = BooleanNetwork::try_from("$A: p\n$B: q\n").unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let unit = graph.mk_unit_colors();
+        let attribute = |name: &str| {
+            let parameter = network.find_parameter(name).unwrap();
+            let is_true = graph
+                .symbolic_context()
+                .mk_uninterpreted_function_is_true(parameter, &[]);
+            let positive = unit.copy(unit.as_bdd().and(&is_true));
+            Attribute {
+                name: name.to_string(),
+                kind: AttributeKind::ConstantParameter { parameter },
+                negative: unit.minus(&positive),
+                positive,
+                context: None,
+            }
+        };
+
+        // The class is fully determined by `p`, while `q` is independent of it.
+        let p_attribute = attribute("p");
+        let classes = HashMap::from([
+            (
+                Class::try_read_string("S").unwrap(),
+                p_attribute.positive.clone(),
+            ),
+            (
+                Class::try_read_string("D").unwrap(),
+                p_attribute.negative.clone(),
+            ),
+        ]);
+        let tree = Bdt::new(
+            classes,
+            vec![attribute("q"), p_attribute, attribute("q")],
+            &network,
+        );
+        // Attribute kinds resolve their names through the network.
+        let kind = tree.attribute_kind_json(tree.attributes().nth(1).unwrap());
+        assert_eq!(kind["parameter"]["name"], "p");
+
+        let ranking = tree.attribute_ranking();
+        let order = ranking
+            .iter()
+            .map(|it| it.attribute.to_index())
+            .collect::<Vec<_>>();
+        // Ties keep the original attribute order.
+        assert_eq!(order, vec![1, 0, 2]);
+        assert!((ranking[0].mutual_information - 1.0).abs() < 1e-9);
+        assert_eq!(ranking[1].mutual_information, 0.0);
+        assert!(ranking[0].classes.iter().all(|it| it.is_separated()));
+    }
+}
@@ -4,6 +4,7 @@ use crate::bdt::{
 };
 use crate::scc::Class;
 use crate::util::functional::Functional;
+use biodivine_lib_param_bn::BooleanNetwork;
 use biodivine_lib_param_bn::biodivine_std::traits::Set;
 use biodivine_lib_param_bn::symbolic_async_graph::GraphColors;
 use std::collections::{HashMap, HashSet};
@@ -11,12 +12,26 @@ use std::option::Option::Some;
 
 impl Bdt {
     /// Create a new single-node tree for given classification and attributes.
-    pub fn new(classes: BifurcationFunction, attributes: Vec<Attribute>) -> Bdt {
+    ///
+    /// The `network` provides the variable and parameter names used by the attribute kinds.
+    pub fn new(
+        classes: BifurcationFunction,
+        attributes: Vec<Attribute>,
+        network: &BooleanNetwork,
+    ) -> Bdt {
         Bdt {
             attributes,
             storage: HashMap::new(),
             next_id: 0,
             precision: None,
+            variable_names: network
+                .variables()
+                .map(|v| network.get_variable_name(v).clone())
+                .collect(),
+            parameter_names: network
+                .parameters()
+                .map(|p| network[p].get_name().clone())
+                .collect(),
         }
         .apply(|t| t.insert_node_with_classes(classes))
     }
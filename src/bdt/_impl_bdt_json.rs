@@ -115,10 +115,20 @@ impl Bdt {
                 result
                     .insert("attribute_name", self[attr_id].name.clone())
                     .unwrap();
+                result
+                    .insert("attribute_kind", self.attribute_kind_json(attr_id))
+                    .unwrap();
             }
         })
     }
 
+    /// Convert the kind of the given attribute to JSON, resolving variable and parameter names.
+    pub fn attribute_kind_json(&self, id: AttributeId) -> JsonValue {
+        self[id]
+            .kind
+            .to_json(&self.variable_names, &self.parameter_names)
+    }
+
     /// Compute attribute gains for the given tree node.
     pub fn attribute_gains_json(&self, id: BdtNodeId) -> JsonValue {
         self.applied_attributes(id)
@@ -127,6 +137,7 @@ impl Bdt {
                 object! {
                     "id" => it.attribute.to_index(),
                     "name" => self[it.attribute].name.clone(),
+                    "kind" => self.attribute_kind_json(it.attribute),
                     "left" => class_list_to_json(&it.left),
                     "right" => class_list_to_json(&it.right),
                     "gain" => it.information_gain
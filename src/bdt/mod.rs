@@ -1,6 +1,7 @@
 use crate::scc::Class;
-use biodivine_lib_param_bn::VariableId;
 use biodivine_lib_param_bn::symbolic_async_graph::GraphColors;
+use biodivine_lib_param_bn::{ParameterId, VariableId};
+use num_bigint::BigUint;
 use std::collections::HashMap;
 use std::collections::hash_map::Keys;
 use std::iter::Map;
@@ -11,6 +12,10 @@ use std::ops::Range;
 mod _attributes_for_network;
 /// **(internal)** Some utility functions for working with attributes.
 mod _impl_attribute;
+/// **(internal)** Implementation of JSON serialization and naming of attribute kinds.
+mod _impl_attribute_kind;
+/// **(internal)** Implementation of the global attribute ranking over the whole parameter space.
+mod _impl_attribute_ranking;
 /// **(internal)** Implementation of utility methods for the binary decision tree.
 mod _impl_bdt;
 /// **(internal)** Implementation of .dot export utilities for a decision tree.
@@ -75,6 +80,10 @@ pub struct Bdt {
     // is assumed to be a leaf, or `None` is the tree exact. We assume that this number is
     // always >50% to make sure the decision is unique.
     precision: Option<u32>,
+    // Names of network variables and parameters (indexed by their ids). These are used
+    // to resolve the structured payload of attribute kinds when exporting the tree.
+    variable_names: Vec<String>,
+    parameter_names: Vec<String>,
 }
 
 type BdtNodeIds<'a> = Map<Keys<'a, usize, BdtNode>, fn(&usize) -> BdtNodeId>;
@@ -85,11 +94,58 @@ type AttributeIds<'a> = Map<Range<usize>, fn(usize) -> AttributeId>;
 #[derive(Clone)]
 pub struct Attribute {
     name: String,
+    kind: AttributeKind,
     positive: GraphColors,
     negative: GraphColors,
     context: Option<AttributeContext>,
 }
 
+/// Describes what kind of network property an `Attribute` represents, together with
+/// the structured data (variables, parameters, table rows) that identify the property.
+///
+/// The `name` of an attribute is only meant for humans; clients that want to render, group
+/// or localize attributes should use the kind instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AttributeKind {
+    /// Value of an input variable (a variable with no regulators and no update function).
+    Input { variable: VariableId },
+    /// Value of a zero-arity parameter.
+    ConstantParameter { parameter: ParameterId },
+    /// The `regulator` is essential (observable) in the update function of `target`.
+    Essentiality {
+        regulator: VariableId,
+        target: VariableId,
+    },
+    /// The `regulator` acts as an activator in the update function of `target`.
+    Activation {
+        regulator: VariableId,
+        target: VariableId,
+    },
+    /// The `regulator` acts as an inhibitor in the update function of `target`.
+    Inhibition {
+        regulator: VariableId,
+        target: VariableId,
+    },
+    /// One row of an implicit (unspecified) update function of `target`. The row is
+    /// identified by the valuation of its regulators.
+    ImplicitTableRow {
+        target: VariableId,
+        valuation: Vec<(VariableId, bool)>,
+    },
+    /// One row of an explicit uninterpreted function. The row is identified by the
+    /// valuation of the function arguments.
+    ExplicitTableRow {
+        parameter: ParameterId,
+        valuation: Vec<bool>,
+    },
+    /// The `regulator` is essential in `target`, but only when the `condition` holds.
+    ConditionalEssentiality {
+        regulator: VariableId,
+        target: VariableId,
+        condition: Vec<(VariableId, bool)>,
+    },
+}
+
 /// A property of essential attributes that allows us to say when a certain attribute is
 /// superseded by its more specific version.
 #[derive(Clone)]
@@ -112,6 +168,35 @@ pub struct AppliedAttribute {
     pub information_gain: f64,
 }
 
+/// A global summary of how much one attribute explains the class variation in the whole
+/// parameter space of a `Bdt` (i.e. in its root node).
+#[derive(Clone)]
+pub struct AttributeRanking {
+    pub attribute: AttributeId,
+    /// Information gain of the attribute in the root node (same as in `AppliedAttribute`).
+    pub information_gain: f64,
+    /// Mutual information between the attribute and the whole class variable.
+    pub mutual_information: f64,
+    /// Exact number of root colours that satisfy the attribute.
+    pub positive_cardinality: BigUint,
+    /// Exact number of root colours that do not satisfy the attribute.
+    pub negative_cardinality: BigUint,
+    /// Per-class breakdown of the attribute effect, ordered by class.
+    pub classes: Vec<ClassRanking>,
+}
+
+/// Describes the relationship between one attribute and one behaviour class.
+#[derive(Clone)]
+pub struct ClassRanking {
+    pub class: Class,
+    /// Mutual information between the attribute and the indicator of this class.
+    pub mutual_information: f64,
+    /// Exact number of class colours that satisfy the attribute.
+    pub positive_cardinality: BigUint,
+    /// Exact number of class colours that do not satisfy the attribute.
+    pub negative_cardinality: BigUint,
+}
+
 /// Compute entropy of the behavior class data set
 pub fn entropy(classes: &BifurcationFunction) -> f64 {
     if classes.is_empty() {
@@ -0,0 +1,187 @@
+use biodivine_aeon_server::control::{ControlComputation, ControlRequest};
+use json::{JsonValue, object};
+use std::io::Read;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+const USAGE: &str = "Usage: control [--controllable A,B] [--phenotype A=1,B=0]... \
+[--phenotype-expression \"A & !B\"] [--quantifier all|any] \
+[--source A=1,B=0] [--mode permanent|temporary|reprogramming] \
+[--oscillation allowed|required|forbidden] [--max-size N] [--min-robustness R] \
+[--result-count N] [--format csv|json] < model.aeon";
+
+/// Compute perturbations controlling the model given on standard input and write
+/// the result table to standard output (progress is reported on standard error).
+///
+/// The controllable variables, phenotype and source state are read from the `#!control`
+/// and `#!control_source` model annotations, unless at least one of `--controllable`,
+/// `--phenotype`, `--phenotype-expression` or `--source` is given. In that case, only
+/// the arguments are used and all variables are controllable by default. The `--phenotype`
+/// option can be repeated to give alternative phenotype subspaces, or the phenotype can be
+/// given as a Boolean expression using `--phenotype-expression`.
+///
+/// Invalid arguments (and `--help`) print the usage to standard error and exit with code 1.
+fn main() {
+    let mut controllable: Option<String> = None;
+    let mut phenotype: Vec<String> = Vec::new();
+    let mut phenotype_expression: Option<String> = None;
+    let mut quantifier = "all".to_string();
+    let mut source: Option<String> = None;
+    let mut mode = "permanent".to_string();
+    let mut oscillation = "allowed".to_string();
+    let mut max_size = 1usize;
+    let mut min_robustness = 0.0f64;
+    let mut result_count = 100usize;
+    let mut format = "csv".to_string();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--help" || arg == "-h" {
+            exit_with_usage(None);
+        }
+        let Some(value) = args.next() else {
+            exit_with_usage(Some(&format!("Missing value of `{arg}`.")));
+        };
+        match arg.as_str() {
+            "--controllable" => controllable = Some(value),
+            "--phenotype" => phenotype.push(value),
+            "--phenotype-expression" => phenotype_expression = Some(value),
+            "--quantifier" => quantifier = value,
+            "--source" => source = Some(value),
+            "--mode" => mode = value,
+            "--oscillation" => oscillation = value,
+            "--max-size" => max_size = parse_value(&arg, &value),
+            "--min-robustness" => min_robustness = parse_value(&arg, &value),
+            "--result-count" => result_count = parse_value(&arg, &value),
+            "--format" => format = value,
+            _ => exit_with_usage(Some(&format!("Unknown argument `{arg}`."))),
+        }
+    }
+
+    if format != "csv" && format != "json" {
+        exit_with_usage(Some(&format!("Unknown format `{format}`.")));
+    }
+    if phenotype_expression.is_some() && !phenotype.is_empty() {
+        exit_with_usage(Some(
+            "Use either `--phenotype` or `--phenotype-expression`, not both.",
+        ));
+    }
+
+    let mut buffer = String::new();
+    if let Err(error) = std::io::stdin().read_to_string(&mut buffer) {
+        exit_with_error(&format!("Cannot read the model: {error}"));
+    }
+
+    let start = SystemTime::now();
+
+    let use_annotations = controllable.is_none()
+        && phenotype.is_empty()
+        && phenotype_expression.is_none()
+        && source.is_none();
+    let request = if use_annotations {
+        ControlRequest::from_annotations(
+            buffer,
+            &mode,
+            &quantifier,
+            &oscillation,
+            min_robustness,
+            max_size,
+            result_count,
+        )
+    } else {
+        let mut request = object! {
+            "model" => buffer,
+            "mode" => mode,
+            "quantifier" => quantifier,
+            "oscillation" => oscillation,
+            "max_size" => max_size,
+            "min_robustness" => min_robustness,
+            "result_count" => result_count,
+            "phenotype" => phenotype.iter().map(|it| read_subspace("--phenotype", it)).collect::<Vec<_>>(),
+        };
+        if let Some(expression) = phenotype_expression {
+            request["phenotype"] = expression.into();
+        }
+        if let Some(controllable) = controllable {
+            let names = controllable
+                .split(',')
+                .map(|it| it.trim())
+                .collect::<Vec<_>>();
+            request["controllable"] = names.into();
+        }
+        if let Some(source) = source {
+            request["source"] = read_subspace("--source", &source);
+        }
+        ControlRequest::from_json(&request)
+    };
+    let request = request.unwrap_or_else(|e| exit_with_error(&e));
+
+    eprintln!("Controllable variables: {:?}", request.controllable_names());
+    eprintln!(
+        "Phenotype: {} ({})",
+        request.phenotype_expression, request.quantifier
+    );
+
+    let computation = ControlComputation::new(request);
+    computation.run();
+
+    if format == "csv" {
+        print!("{}", computation.export_csv());
+    } else {
+        println!("{}", computation.export_json().pretty(2));
+    }
+
+    eprintln!(
+        "Control completed. Perturbations: {}",
+        computation.results().len()
+    );
+    eprintln!("Elapsed time: {}s", start.elapsed().unwrap().as_secs());
+}
+
+/// Print the error `message` (if any) and the usage to standard error, then exit.
+fn exit_with_usage(message: Option<&str>) -> ! {
+    if let Some(message) = message {
+        eprintln!("{message}");
+    }
+    eprintln!("{USAGE}");
+    std::process::exit(1);
+}
+
+/// Print the error `message` to standard error, then exit.
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("{message}");
+    std::process::exit(1);
+}
+
+/// Parse the `value` of the command line option `arg`, or exit if it is invalid.
+fn parse_value<T: FromStr>(arg: &str, value: &str) -> T {
+    value
+        .parse()
+        .unwrap_or_else(|_| exit_with_usage(Some(&format!("Invalid value of `{arg}`: `{value}`."))))
+}
+
+/// Read a subspace of the command line option `arg` given as a comma-separated list of
+/// `name=value` pairs, where the value is `1`/`true` or `0`/`false`. Exits if the subspace
+/// is invalid.
+fn read_subspace(arg: &str, subspace: &str) -> JsonValue {
+    let mut result = JsonValue::new_object();
+    for item in subspace
+        .split(',')
+        .map(|it| it.trim())
+        .filter(|it| !it.is_empty())
+    {
+        let Some((name, value)) = item.split_once('=') else {
+            let message = format!("Invalid `{arg}` item `{item}`. Expected `name=value`.");
+            exit_with_usage(Some(&message));
+        };
+        result[name.trim()] = match value.trim() {
+            "1" | "true" => true.into(),
+            "0" | "false" => false.into(),
+            _ => {
+                let message = format!("Invalid `{arg}` value of `{name}`: `{value}`.");
+                exit_with_usage(Some(&message))
+            }
+        };
+    }
+    result
+}
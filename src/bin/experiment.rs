@@ -1,5 +1,6 @@
 use biodivine_aeon_server::GraphTaskContext;
 use biodivine_aeon_server::scc::Classifier;
+use biodivine_aeon_server::scc::algo_trap_spaces::{TrapSpaceKind, TrapSpaceSet};
 use biodivine_algo_bdd_scc::attractor::{
     AttractorConfig, InterleavedTransitionGuidedReduction, ItgrState, XieBeerelAttractors,
 };
@@ -40,6 +41,22 @@ fn main() {
         graph.unit_colored_vertices().approx_cardinality()
     );
 
+    // Minimal trap spaces are cheap to compute and each contains at least one attractor,
+    // so they give a quick lower bound on the number of attractors.
+    let trap_spaces = TrapSpaceSet::compute(&model, TrapSpaceKind::Minimal).unwrap();
+    println!(
+        "Minimal trap spaces: {} (space-colour pairs: {})",
+        trap_spaces.approx_space_count(),
+        trap_spaces.approx_cardinality()
+    );
+    for space in trap_spaces.enumerate(&graph, 10) {
+        let space_json = space.to_json(&graph);
+        println!(
+            " > {} for {} parametrizations",
+            space_json["space"], space_json["cardinality"]
+        );
+    }
+
     let classifier = Classifier::new(&graph);
     let task_context = GraphTaskContext::new();
     task_context.init_progress(&graph);
@@ -1,25 +1,49 @@
 use biodivine_aeon_server::GraphTaskContext;
-use biodivine_aeon_server::scc::{Behaviour, Classifier};
-use biodivine_algo_bdd_scc::attractor::{
-    AttractorConfig, InterleavedTransitionGuidedReduction, ItgrState, XieBeerelAttractors,
-};
+use biodivine_aeon_server::scc::Classifier;
+use biodivine_aeon_server::scc::algo_attractor_search::attractor_search;
+use biodivine_aeon_server::scc::algo_fixed_points::projected_fixed_points;
 use biodivine_lib_param_bn::BooleanNetwork;
-use biodivine_lib_param_bn::biodivine_std::bitvector::BitVector;
-use biodivine_lib_param_bn::biodivine_std::traits::Set;
 use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
-use computation_process::{Computable, Stateful};
-use std::collections::BTreeSet;
 use std::convert::TryFrom;
 use std::io::Read;
 use std::time::SystemTime;
 
+const USAGE: &str = "Usage: sink_state_enumerator [--classify] [A,B,...] < model.aeon";
+
+/// Enumerate all sink states of the model given on standard input.
+///
+/// Optionally, the program takes a comma-separated list of variable names as an argument.
+/// The sinks are then projected onto these variables. Fixed points are computed directly,
+/// hence the full attractor search only runs with `--classify`, which also prints
+/// the behaviour classes of all attractors (as earlier versions of this program did).
+///
+/// Invalid arguments (and `--help`) print the usage to standard error and exit with code 1.
 fn main() {
+    let mut classify = false;
+    let mut variable_list: Option<String> = None;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--help" | "-h" => exit_with_usage(None),
+            "--classify" => classify = true,
+            _ if arg.starts_with("--") => {
+                exit_with_usage(Some(&format!("Unknown argument `{arg}`.")))
+            }
+            _ if variable_list.is_some() => {
+                exit_with_usage(Some(&format!("Unexpected argument `{arg}`.")))
+            }
+            _ => variable_list = Some(arg),
+        }
+    }
+
     let mut buffer = String::new();
-    std::io::stdin().read_to_string(&mut buffer).unwrap();
+    if let Err(error) = std::io::stdin().read_to_string(&mut buffer) {
+        exit_with_error(&format!("Cannot read the model: {error}"));
+    }
 
     let start = SystemTime::now();
 
-    let model = BooleanNetwork::try_from(buffer.as_str()).unwrap();
+    let model = BooleanNetwork::try_from(buffer.as_str())
+        .unwrap_or_else(|error| exit_with_error(&format!("Invalid model: {error}")));
     let names: Vec<_> = model
         .variables()
         .map(|id| model.get_variable_name(id))
@@ -27,7 +51,8 @@ fn main() {
     println!("Model loaded...");
     println!("{} variables: {:?}", model.num_vars(), names);
 
-    let graph = SymbolicAsyncGraph::new(&model).unwrap();
+    let graph = SymbolicAsyncGraph::new(&model)
+        .unwrap_or_else(|error| exit_with_error(&format!("Invalid model: {error}")));
 
     println!("Asynchronous graph ready...");
     println!(
@@ -39,25 +64,47 @@ fn main() {
         graph.unit_colored_vertices().approx_cardinality()
     );
 
-    let classifier = Classifier::new(&graph);
-    let task_context = GraphTaskContext::new();
-    task_context.init_progress(&graph);
+    let variables = match variable_list {
+        None => graph.variables().collect::<Vec<_>>(),
+        Some(list) => list
+            .split(',')
+            .map(|name| {
+                let name = name.trim();
+                model.as_graph().find_variable(name).unwrap_or_else(|| {
+                    exit_with_usage(Some(&format!("Unknown variable `{name}`.")))
+                })
+            })
+            .collect(),
+    };
 
-    // Now we can actually start the computation...
+    if classify {
+        print_classes(&graph);
+    }
 
-    // First, perform ITGR reduction.
-    let state = ItgrState::new(&graph, graph.unit_colored_vertices());
-    let mut itgr = InterleavedTransitionGuidedReduction::configure(&graph, state);
-    let universe = itgr.compute().expect("Cancellation disabled.");
-    let active_variables = itgr.state().active_variables().collect::<BTreeSet<_>>();
+    // Fixed points are computed directly, i.e. we do not need the full attractor search.
+    let sinks = projected_fixed_points(&graph, graph.unit_colored_vertices(), variables);
 
-    // Then run Xie-Beerel to actually detect the components.
-    let mut config = AttractorConfig::new(graph.clone());
-    config.active_variables = active_variables;
-    let attractors = XieBeerelAttractors::configure(config, universe);
+    println!("Explicit sinks:");
+    let mut count = 0;
+    for item in sinks {
+        for (var, value) in &item.valuation {
+            print!("{}: {}; ", model.get_variable_name(*var), value);
+        }
+        println!("{} instance(s).", item.set.colors().approx_cardinality());
+        count += 1;
+    }
+
+    println!("Analysis completed. Projected sinks: {}", count);
+    println!("Elapsed time: {}s", start.elapsed().unwrap().as_secs());
+}
 
-    for component in attractors {
-        let component = component.expect("Cancellation disabled.");
+/// Run the full attractor search and print the behaviour classes of all attractors.
+fn print_classes(graph: &SymbolicAsyncGraph) {
+    let classifier = Classifier::new(graph);
+    let task_context = GraphTaskContext::new();
+    task_context.init_progress(graph);
+
+    attractor_search(graph, &task_context, |component| {
         println!("Found attractor... {}", component.approx_cardinality());
         println!("Remaining: {}", task_context.get_progress_string());
         println!(
@@ -65,42 +112,25 @@ fn main() {
             component.vertices().approx_cardinality()
         );
         println!("Unique colors: {}", component.colors().approx_cardinality());
-        classifier.add_component(component, &graph);
-    }
+        classifier.add_component(component, graph);
+    })
+    .expect("Cancellation disabled.");
 
     classifier.print();
+    println!("Classes: {}", classifier.export_result().len());
+}
 
-    println!(
-        "Analysis completed. Classes: {}",
-        classifier.export_result().len()
-    );
-    println!("Elapsed time: {}s", start.elapsed().unwrap().as_secs());
-
-    let mut all_sinks = graph.mk_empty_colored_vertices();
-    for (attractor, behaviours) in classifier.export_components() {
-        if let Some(sink_colors) = behaviours.get(&Behaviour::Stability) {
-            all_sinks = all_sinks.union(&attractor.intersect_colors(sink_colors));
-        }
+/// Print the error `message` (if any) and the usage to standard error, then exit.
+fn exit_with_usage(message: Option<&str>) -> ! {
+    if let Some(message) = message {
+        eprintln!("{message}");
     }
+    eprintln!("{USAGE}");
+    std::process::exit(1);
+}
 
-    println!(
-        "Explicit sinks ({} states):",
-        all_sinks.vertices().approx_cardinality()
-    );
-
-    for vertex in all_sinks.vertices().materialize().iter() {
-        let values = model
-            .variables()
-            .enumerate()
-            .map(|(i, v)| (model.get_variable_name(v).clone(), vertex.get(i)))
-            .collect::<Vec<_>>();
-        for (name, value) in values {
-            print!("{}: {}; ", name, value);
-        }
-        let sink = graph.vertex(&vertex);
-        println!(
-            "{} instance(s).",
-            sink.intersect(&all_sinks).approx_cardinality()
-        );
-    }
+/// Print the error `message` to standard error, then exit.
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("{message}");
+    std::process::exit(1);
 }
@@ -0,0 +1,594 @@
+use crate::control::_impl_control_progress::perturbation_count;
+use crate::control::_impl_explicit_control::phenotype_vertices;
+use crate::control::{
+    ControlComputation, ControlMode, ControlRequest, ControlResult, PhenotypeQuantifier,
+};
+use biodivine_lib_param_bn::biodivine_std::bitvector::ArrayBitVector;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::GraphColors;
+use biodivine_lib_param_bn::{BooleanNetwork, VariableId};
+use biodivine_pbn_control::control::{ControlMap, PhenotypeOscillationType};
+use biodivine_pbn_control::perturbation::PerturbationGraph;
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+impl ControlComputation {
+    /// Create a new (not yet running) computation for the given request.
+    ///
+    /// The `request.restriction` refers to results of another computation, hence it has to be
+    /// resolved by the caller and assigned to `restriction`.
+    pub fn new(request: ControlRequest) -> ControlComputation {
+        let names = |var: &VariableId| request.network.get_variable_name(*var).clone();
+        let costs = request
+            .costs
+            .iter()
+            .map(|(var, cost)| (names(var), *cost))
+            .collect();
+        let allowed_values = request
+            .allowed_values
+            .iter()
+            .map(|(var, values)| (names(var), values.clone()))
+            .collect();
+        // Variables without any allowed value can never be perturbed, hence they are excluded
+        // from the search space entirely.
+        let controllable = request
+            .controllable
+            .iter()
+            .filter(|var| {
+                request
+                    .allowed_values
+                    .get(var)
+                    .is_none_or(|values| !values.is_empty())
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        let graph =
+            PerturbationGraph::with_restricted_variables(&request.network, controllable.clone());
+        ControlComputation {
+            timestamp: SystemTime::now(),
+            finished_timestamp: None,
+            input_model: request.model,
+            graph,
+            thread: None,
+            mode: request.mode,
+            source: request.source,
+            controllable,
+            phenotype: request.phenotype,
+            phenotype_expression: request.phenotype_expression,
+            quantifier: request.quantifier,
+            results: Mutex::new(Vec::new()),
+            oscillation: request.oscillation,
+            max_size: request.max_size,
+            min_robustness: request.min_robustness,
+            result_count: request.result_count,
+            costs,
+            allowed_values,
+            max_cost: request.max_cost,
+            restriction: None,
+            robustness_cache: Mutex::new(HashMap::new()),
+            search_progress: Default::default(),
+            is_cancelled: cancel_this::CancelAtomic::new(),
+        }
+    }
+
+    pub fn start_timestamp(&self) -> u128 {
+        self.timestamp
+            .duration_since(UNIX_EPOCH)
+            .expect("Time error")
+            .as_millis()
+    }
+
+    pub fn end_timestamp(&self) -> Option<u128> {
+        self.finished_timestamp.map(|t| {
+            t.duration_since(UNIX_EPOCH)
+                .expect("Time error")
+                .as_millis()
+        })
+    }
+
+    pub fn elapsed_millis(&self) -> u64 {
+        let elapsed = self
+            .finished_timestamp
+            .unwrap_or_else(SystemTime::now)
+            .duration_since(self.timestamp)
+            .unwrap();
+        u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+    }
+
+    pub fn results(&self) -> MutexGuard<'_, Vec<ControlResult>> {
+        self.results
+            .lock()
+            .unwrap_or_else(|_| panic!("Control results are poisoned."))
+    }
+
+    /// The fraction of all (non-perturbable) colors that are controlled by the given
+    /// perturbation colors.
+    pub fn robustness(&self, colors: &GraphColors) -> f64 {
+        let unit = self.graph.as_non_perturbable().mk_unit_colors();
+        robustness_within(colors, &unit)
+    }
+
+    /// The fraction of the `restriction` colors that are controlled by the given
+    /// perturbation colors.
+    pub fn restricted_robustness(&self, colors: &GraphColors, restriction: &GraphColors) -> f64 {
+        robustness_within(&colors.intersect(restriction), restriction)
+    }
+
+    /// Robustness within `restriction` if it is set, otherwise the global robustness.
+    pub fn effective_robustness(&self, colors: &GraphColors) -> f64 {
+        match &self.restriction {
+            Some(restriction) => self.restricted_robustness(colors, restriction),
+            None => self.robustness(colors),
+        }
+    }
+
+    /// Effective robustness of the given result, computed only once for each result.
+    pub fn cached_robustness(&self, (perturbation, colors): &ControlResult) -> f64 {
+        let mut key = perturbation
+            .iter()
+            .map(|(name, value)| (name.clone(), *value))
+            .collect::<Vec<_>>();
+        key.sort();
+        let cached = self
+            .robustness_cache
+            .lock()
+            .unwrap()
+            .get(&key)
+            .filter(|(cached_colors, _)| cached_colors == colors)
+            .map(|(_, robustness)| *robustness);
+        if let Some(robustness) = cached {
+            return robustness;
+        }
+        let robustness = self.effective_robustness(colors);
+        self.robustness_cache
+            .lock()
+            .unwrap()
+            .insert(key, (colors.clone(), robustness));
+        robustness
+    }
+
+    /// Total cost of the given perturbation.
+    pub fn cost(&self, perturbation: &HashMap<String, bool>) -> f64 {
+        perturbation
+            .keys()
+            .map(|name| self.costs.get(name).cloned().unwrap_or(1.0))
+            .sum()
+    }
+
+    /// True if all perturbed variables use an allowed value and the total cost is within
+    /// `max_cost`.
+    pub fn is_allowed(&self, perturbation: &HashMap<String, bool>) -> bool {
+        let values_allowed = perturbation.iter().all(|(name, value)| {
+            self.allowed_values
+                .get(name)
+                .map(|allowed| allowed.contains(value))
+                .unwrap_or(true)
+        });
+        let cost_allowed = self
+            .max_cost
+            .map(|max_cost| self.cost(perturbation) <= max_cost)
+            .unwrap_or(true);
+        values_allowed && cost_allowed
+    }
+
+    /// The largest perturbation size that is searched: at most `max_size`, and such that
+    /// the cheapest perturbation of this size is within `max_cost`.
+    pub fn size_limit(&self) -> usize {
+        let Some(max_cost) = self.max_cost else {
+            return self.max_size;
+        };
+        let graph = self.graph.as_non_perturbable();
+        let mut costs = self
+            .controllable
+            .iter()
+            .map(|var| {
+                let name = graph.get_variable_name(*var);
+                self.costs.get(name).cloned().unwrap_or(1.0)
+            })
+            .collect::<Vec<_>>();
+        costs.sort_by(f64::total_cmp);
+        let mut total = 0.0;
+        let mut size = 0;
+        for cost in costs.into_iter().take(self.max_size) {
+            total += cost;
+            if total > max_cost {
+                break;
+            }
+            size += 1;
+        }
+        size
+    }
+
+    /// Replace the current results with the admissible subset of `found`, i.e. the allowed
+    /// perturbations that satisfy `min_robustness`, up to `result_count` items. If
+    /// `restriction` is set, robustness within the restriction is used.
+    ///
+    /// The results are ordered by increasing cost, then by decreasing robustness. Note that
+    /// the search itself proceeds by increasing perturbation size, hence the ranking only
+    /// covers the sizes searched before `result_count` is reached: a cheaper but larger
+    /// perturbation is not found once enough smaller perturbations are admissible.
+    /// Returns `true` if the result limit has been reached.
+    pub fn update_results(&self, found: &[ControlResult]) -> bool {
+        let mut admissible = found
+            .iter()
+            .filter(|(perturbation, _)| self.is_allowed(perturbation))
+            .map(|result| (self.cost(&result.0), self.cached_robustness(result), result))
+            .filter(|(_, robustness, _)| *robustness >= self.min_robustness)
+            .collect::<Vec<_>>();
+        admissible.sort_by(|(c1, r1, _), (c2, r2, _)| c1.total_cmp(c2).then(r2.total_cmp(r1)));
+        let best_robustness = admissible
+            .iter()
+            .map(|(_, robustness, _)| *robustness)
+            .reduce(f64::max);
+        let admissible = admissible
+            .into_iter()
+            .take(self.result_count)
+            .map(|(_, _, result)| result.clone())
+            .collect::<Vec<_>>();
+        let is_full = admissible.len() >= self.result_count;
+        self.search_progress.set_best_robustness(best_robustness);
+        *self.results() = admissible;
+        is_full
+    }
+
+    /// Indices of `results` that form the cost/robustness Pareto front, i.e. no other result
+    /// has lower (or equal) cost and higher (or equal) robustness.
+    ///
+    /// The `results` must be ordered as in `update_results`.
+    pub fn pareto_front(&self, results: &[ControlResult]) -> Vec<usize> {
+        // Results are sorted by cost and then by robustness, hence a result is on the front
+        // iff it is more robust than all cheaper results.
+        let mut front = Vec::new();
+        let mut best_robustness = f64::NEG_INFINITY;
+        for (i, result) in results.iter().enumerate() {
+            let robustness = self.cached_robustness(result);
+            if robustness > best_robustness {
+                best_robustness = robustness;
+                front.push(i);
+            }
+        }
+        front
+    }
+
+    /// Progress of the computation as a fraction of the requested `result_count`.
+    ///
+    /// Once the computation is finished, the progress is always `1.0`.
+    pub fn progress(&self) -> f64 {
+        if self.thread.is_none() {
+            return 1.0;
+        }
+        let found = self.results().len() as f64;
+        (found / self.result_count as f64).min(1.0)
+    }
+
+    /// Run the control algorithm selected by `mode` and `quantifier` and store its results.
+    ///
+    /// Permanent control of all attractors uses the symbolic algorithm of `pbn-control`,
+    /// the remaining combinations enumerate perturbations explicitly (see `run_explicit`).
+    /// In all modes, the target is the set of states satisfying the `phenotype` expression,
+    /// and the attractors reachable from the `source` state (if given) are checked.
+    ///
+    /// Temporary and reprogramming control of all attractors also use the algorithms of
+    /// `pbn-control` when the phenotype is a single (target attractor) state and oscillation
+    /// is allowed, as this is exactly the problem solved by these algorithms.
+    pub fn run(&self) {
+        let is_attractor_problem = self.quantifier == PhenotypeQuantifier::All
+            && matches!(self.oscillation, PhenotypeOscillationType::Allowed);
+        let results = match self.mode {
+            ControlMode::Permanent if self.quantifier == PhenotypeQuantifier::All => {
+                self.run_permanent()
+            }
+            ControlMode::Temporary | ControlMode::Reprogramming if is_attractor_problem => {
+                match self.phenotype_state() {
+                    Some(target) => self.run_attractor(&target),
+                    None => self.run_explicit(),
+                }
+            }
+            _ => self.run_explicit(),
+        };
+        self.update_results(&results);
+    }
+
+    pub(super) fn run_permanent(&self) -> Vec<ControlResult> {
+        let pstg = &self.graph;
+        let phenotype = phenotype_vertices(pstg.as_original(), &self.phenotype).vertices();
+
+        // Allowed values are not part of the perturbation colours, hence variables with one
+        // allowed value are filtered in `update_results`.
+        let mut admissible_perturbations = pstg.mk_empty_colors();
+        for i in 0..=self.size_limit() {
+            let size_perturbations = pstg.create_perturbation_colors(i, true);
+            admissible_perturbations = admissible_perturbations.union(&size_perturbations);
+        }
+
+        let candidates = (0..=self.size_limit())
+            .map(|size| self.candidate_count(size))
+            .collect::<Vec<_>>();
+        let results = pstg.phenotype_permanent_control_iterated(
+            &phenotype,
+            self.oscillation,
+            Some(&admissible_perturbations),
+            pstg.mk_unit_colored_vertices().vertices(),
+            // With a restriction, the threshold applies to the restricted robustness, hence
+            // the library cannot prune results by their global robustness.
+            if self.restriction.is_some() {
+                None
+            } else {
+                Some(self.min_robustness)
+            },
+            // The results are further filtered by cost and allowed values, hence we rely
+            // on `update_results` to stop the computation once enough results are found.
+            None,
+            true,
+            |partial| {
+                // The callback is expected once all perturbations of the next size are
+                // processed (these are evaluated symbolically as a whole). Extra calls are
+                // not counted, and missing sizes are counted once the search completes.
+                let size = self.search_progress.sizes_processed.load(Ordering::SeqCst);
+                if let Some(count) = candidates.get(size) {
+                    self.search_progress.finish_size(*count);
+                }
+                // Stream the partial results into the session and stop early once
+                // enough admissible perturbations are found.
+                let is_full = self.update_results(partial);
+                if is_full || self.is_cancelled.is_cancelled() {
+                    Err(partial.clone())
+                } else {
+                    Ok(())
+                }
+            },
+        );
+
+        match results {
+            Ok(results) => {
+                let size = self.search_progress.sizes_processed.load(Ordering::SeqCst);
+                for count in candidates.iter().skip(size) {
+                    self.search_progress.finish_size(*count);
+                }
+                results
+            }
+            Err(partial) => partial,
+        }
+    }
+
+    /// Number of perturbations of exactly `size` controllable variables, each perturbed
+    /// to one of its allowed values (the cost limit is not considered).
+    pub(super) fn candidate_count(&self, size: usize) -> u64 {
+        let graph = self.graph.as_non_perturbable();
+        let values = self
+            .controllable
+            .iter()
+            .map(|var| {
+                self.allowed_values
+                    .get(graph.get_variable_name(*var))
+                    .map(|values| values.len() as u64)
+                    .unwrap_or(2)
+            })
+            .collect::<Vec<_>>();
+        perturbation_count(&values, size)
+    }
+
+    /// Source-target control. The underlying algorithms are not incremental, hence the
+    /// results only become available once the whole control map is computed.
+    pub(super) fn run_attractor(&self, target: &ArrayBitVector) -> Vec<ControlResult> {
+        let pstg = &self.graph;
+        let source = self
+            .source
+            .as_ref()
+            .and_then(|source| self.pick_state(source))
+            .expect("Source state is required by this control mode.");
+
+        let colors = pstg.mk_unit_colored_vertices().colors();
+        let control_map = match self.mode {
+            ControlMode::Temporary => pstg.one_step_control(&source, target, &colors, false),
+            _ => pstg.permanent_control(&source, target, &colors, false),
+        };
+
+        // See `run_permanent` for why the threshold is not applied with a restriction.
+        let min_robustness = if self.restriction.is_some() {
+            0.0
+        } else {
+            self.min_robustness
+        };
+        control_map
+            .working_perturbations(min_robustness, false, false)
+            .into_iter()
+            .filter(|(perturbation, _)| perturbation.len() <= self.max_size)
+            .collect()
+    }
+
+    /// The only state satisfying the phenotype, or `None` if the phenotype is satisfied
+    /// by multiple (or no) states.
+    pub(super) fn phenotype_state(&self) -> Option<ArrayBitVector> {
+        let phenotype = phenotype_vertices(self.graph.as_original(), &self.phenotype).vertices();
+        if phenotype.approx_cardinality() != 1.0 {
+            return None;
+        }
+        phenotype.materialize().iter().next()
+    }
+
+    /// The state of the original network given by a subspace which fixes all variables
+    /// (as validated by `ControlRequest`).
+    pub(super) fn pick_state(&self, subspace: &[(VariableId, bool)]) -> Option<ArrayBitVector> {
+        self.graph
+            .as_original()
+            .mk_subspace(subspace)
+            .vertices()
+            .materialize()
+            .iter()
+            .next()
+    }
+
+    /// The colours for which the given perturbation does *not* control the network.
+    pub fn failing_colors(&self, colors: &GraphColors) -> GraphColors {
+        self.graph
+            .as_non_perturbable()
+            .mk_unit_colors()
+            .minus(colors)
+    }
+
+    /// Pick a witness network of one parametrization from `colors`. The perturbation can
+    /// be then applied to it using `PerturbationWitness::new` (without holding this
+    /// computation, as the attractor search can take a while).
+    ///
+    /// Returns `None` if `colors` are empty. Witnesses are not supported for `Temporary`
+    /// control, because the attractors of a permanently perturbed network are not relevant there.
+    pub fn witness_network(&self, colors: &GraphColors) -> Result<Option<BooleanNetwork>, String> {
+        if self.mode == ControlMode::Temporary {
+            return Err(format!(
+                "Perturbation witnesses are not supported for `{}` control.",
+                self.mode
+            ));
+        }
+        if colors.is_empty() {
+            return Ok(None);
+        }
+        let witness = self
+            .graph
+            .as_non_perturbable()
+            .pick_witness(&colors.pick_singleton());
+        Ok(Some(witness))
+    }
+}
+
+/// The fraction of `universe` covered by `colors` (assuming `colors` is a subset of `universe`).
+///
+/// The value is always an approximation up to 6 decimal places, even if the
+/// cardinality overflows to `f64::infinity`. For an empty `universe`, the robustness is zero.
+fn robustness_within(colors: &GraphColors, universe: &GraphColors) -> f64 {
+    if universe.is_empty() {
+        return 0.0;
+    }
+    let p_card = colors.exact_cardinality() * 1_000_000u32;
+    let u_card = universe.exact_cardinality();
+    let robustness: BigUint = p_card / u_card;
+    robustness.to_f64().unwrap_or(f64::NAN) / 1_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::control::{ControlComputation, ControlRequest};
+    use std::collections::HashMap;
+
+    // The update function of `A` is unknown, hence the model has multiple colours.
+    const MODEL: &str = "A -> B\nB -|? A\nA ->? A\n";
+
+    fn computation(request: &str) -> ControlComputation {
+        let mut request = json::parse(request).unwrap();
+        request["model"] = MODEL.into();
+        ControlComputation::new(ControlRequest::from_json(&request).unwrap())
+    }
+
+    #[test]
+    fn restricted_robustness_threshold() {
+        let cmp = computation(
+            r#"{ "phenotype": "A", "max_size": 1, "min_robustness": 0.9, "result_count": 5 }"#,
+        );
+        let colors = cmp
+            .graph
+            .as_non_perturbable()
+            .mk_unit_colors()
+            .pick_singleton();
+        let result = (HashMap::from([("A".to_string(), true)]), colors.clone());
+
+        // Globally, a single colour is not robust enough.
+        assert!(cmp.robustness(&colors) < 0.9);
+        cmp.update_results(std::slice::from_ref(&result));
+        assert!(cmp.results().is_empty());
+
+        // Within the restriction, the same colour is fully robust.
+        let mut cmp = computation(
+            r#"{ "phenotype": "A", "max_size": 1, "min_robustness": 0.9, "result_count": 5 }"#,
+        );
+        cmp.restriction = Some(colors.clone());
+        assert_eq!(cmp.effective_robustness(&colors), 1.0);
+        cmp.update_results(std::slice::from_ref(&result));
+        assert_eq!(cmp.results().len(), 1);
+    }
+
+    #[test]
+    fn streamed_results() {
+        let cmp = computation(
+            r#"{ "phenotype": "A", "max_size": 1, "min_robustness": 0, "result_count": 2 }"#,
+        );
+        let unit = cmp.graph.as_non_perturbable().mk_unit_colors();
+        let result = |name: &str| (HashMap::from([(name.to_string(), true)]), unit.clone());
+
+        // Partial results are visible immediately, the limit is only reached with two results.
+        assert!(!cmp.update_results(&[result("A")]));
+        assert_eq!(cmp.results().len(), 1);
+        assert!(cmp.update_results(&[result("A"), result("B")]));
+        assert_eq!(cmp.results().len(), 2);
+        assert_eq!(cmp.robustness_cache.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn perturbation_costs() {
+        let cmp = computation(
+            r#"{ "phenotype": "A", "max_size": 2, "min_robustness": 0, "result_count": 5,
+                 "costs": { "A": 0.5 }, "allowed_values": { "B": [false] }, "max_cost": 1 }"#,
+        );
+        let perturbation = |items: &[(&str, bool)]| {
+            items
+                .iter()
+                .map(|(name, value)| (name.to_string(), *value))
+                .collect::<HashMap<_, _>>()
+        };
+
+        assert_eq!(cmp.cost(&perturbation(&[("A", true), ("B", false)])), 1.5);
+        assert!(cmp.is_allowed(&perturbation(&[("A", false)])));
+        assert!(!cmp.is_allowed(&perturbation(&[("B", true)])));
+        assert!(!cmp.is_allowed(&perturbation(&[("A", true), ("B", false)])));
+        // Any perturbation of size two costs at least 1.5.
+        assert_eq!(cmp.size_limit(), 1);
+        // `A` can be perturbed to both values, `B` only to `false`.
+        assert_eq!(cmp.candidate_count(1), 3);
+        assert_eq!(cmp.candidate_count(2), 2);
+
+        let unit = cmp.graph.as_non_perturbable().mk_unit_colors();
+        let single = unit.pick_singleton();
+        let results = vec![
+            (perturbation(&[]), single.clone()),
+            (perturbation(&[("A", true)]), unit.clone()),
+            (perturbation(&[("B", false)]), single.clone()),
+        ];
+        // The last result is more expensive, but not more robust than the first one.
+        assert_eq!(cmp.pareto_front(&results), vec![0, 1]);
+    }
+
+    #[test]
+    fn permanent_control_progress() {
+        let cmp = computation(
+            r#"{ "phenotype": "A", "max_size": 2, "min_robustness": 0, "result_count": 100,
+                 "allowed_values": { "B": [false] } }"#,
+        );
+        cmp.run_permanent();
+        let progress = cmp.progress_json();
+        assert_eq!(progress["sizesProcessed"].as_usize(), Some(3));
+        // 1 + 3 + 2 candidates of sizes zero, one and two.
+        assert_eq!(progress["candidatesEvaluated"].as_u64(), Some(6));
+    }
+
+    #[test]
+    fn non_perturbable_variables() {
+        let cmp = computation(
+            r#"{ "phenotype": "A", "max_size": 2, "min_robustness": 0, "result_count": 5,
+                 "allowed_values": { "B": [] } }"#,
+        );
+        assert_eq!(cmp.controllable.len(), 1);
+    }
+
+    #[test]
+    fn temporary_control_witness() {
+        let cmp = computation(
+            r#"{ "mode": "temporary", "phenotype": "A", "source": { "A": false, "B": false },
+                 "max_size": 1, "min_robustness": 0, "result_count": 5 }"#,
+        );
+        let unit = cmp.graph.as_non_perturbable().mk_unit_colors();
+        assert!(cmp.witness_network(&unit).is_err());
+    }
+}
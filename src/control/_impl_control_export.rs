@@ -0,0 +1,289 @@
+use crate::control::ControlComputation;
+use json::JsonValue;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Version of the exported control results format (see `ControlComputation::export_json`).
+const EXPORT_SCHEMA_VERSION: u32 = 2;
+
+impl ControlComputation {
+    /// Export the current results as a CSV table with one row per perturbation. There is
+    /// one column for every variable perturbed in at least one result, containing `1`/`0`
+    /// if the variable is perturbed to true/false, or nothing if it is not perturbed.
+    ///
+    /// If the computation has a colour restriction, the results are ranked by the robustness
+    /// within the restriction, which is exported in an extra `restricted_robustness` column.
+    pub fn export_csv(&self) -> String {
+        let results = self.results();
+        let variables = results
+            .iter()
+            .flat_map(|(perturbation, _)| perturbation.keys().cloned())
+            .collect::<BTreeSet<_>>();
+
+        let mut csv = String::from("index,size");
+        for var in &variables {
+            csv += &format!(",{var}");
+        }
+        csv += ",color_count,robustness";
+        if self.restriction.is_some() {
+            csv += ",restricted_robustness";
+        }
+        csv += ",cost\n";
+
+        for (i, result) in results.iter().enumerate() {
+            let (perturbation, colors) = result;
+            csv += &format!("{},{}", i, perturbation.len());
+            for var in &variables {
+                match perturbation.get(var) {
+                    Some(true) => csv += ",1",
+                    Some(false) => csv += ",0",
+                    None => csv += ",",
+                }
+            }
+            csv += &format!(
+                ",{},{}",
+                colors.exact_cardinality(),
+                self.robustness(colors)
+            );
+            if self.restriction.is_some() {
+                csv += &format!(",{}", self.cached_robustness(result));
+            }
+            csv += &format!(",{}\n", self.cost(perturbation));
+        }
+        csv
+    }
+
+    /// Export the current results, together with the input model and the control
+    /// configuration, as a JSON object. The result can be compared with other exports using
+    /// `compare_control_results`.
+    ///
+    /// If the computation has a colour restriction, each result also contains its
+    /// `restricted_robustness` (and `restricted` is `true`).
+    pub fn export_json(&self) -> JsonValue {
+        let results = self
+            .results()
+            .iter()
+            .map(|result| {
+                let (perturbation, colors) = result;
+                let mut item = object! {
+                    "perturbation" => perturbation.clone(),
+                    // Exact cardinality can overflow any JSON number.
+                    "color_count" => colors.exact_cardinality().to_string(),
+                    "robustness" => self.robustness(colors),
+                    "cost" => self.cost(perturbation),
+                };
+                if self.restriction.is_some() {
+                    item["restricted_robustness"] = self.cached_robustness(result).into();
+                }
+                item
+            })
+            .collect::<Vec<_>>();
+        object! {
+            "schema_version" => EXPORT_SCHEMA_VERSION,
+            "mode" => self.mode.to_string(),
+            "model" => self.input_model.clone(),
+            "phenotype" => self.phenotype_expression.clone(),
+            "quantifier" => self.quantifier.to_string(),
+            "min_robustness" => self.min_robustness,
+            "result_count" => self.result_count,
+            "restricted" => self.restriction.is_some(),
+            "is_partial" => self.thread.is_some(),
+            "results" => results,
+        }
+    }
+}
+
+/// Compare two control result exports (see `ControlComputation::export_json`), e.g. for two
+/// variants of a model or phenotype.
+///
+/// Perturbations present only in `right` are reported as `gained`, perturbations present only
+/// in `left` as `lost`. For the remaining perturbations, the change in robustness is reported.
+///
+/// Exports of computations with a colour restriction are compared using the restricted
+/// robustness (which is also what their results were ranked by). Hence, both exports must
+/// either be restricted or unrestricted.
+pub fn compare_control_results(left: &JsonValue, right: &JsonValue) -> Result<JsonValue, String> {
+    let is_restricted = |export: &JsonValue| export["restricted"].as_bool().unwrap_or(false);
+    if is_restricted(left) != is_restricted(right) {
+        return Err("Cannot compare results with and without a colour restriction.".to_string());
+    }
+    let left = read_export(left, "left")?;
+    let right = read_export(right, "right")?;
+
+    let to_json = |perturbation: &BTreeMap<String, bool>| {
+        let mut result = JsonValue::new_object();
+        for (var, value) in perturbation {
+            result[var.as_str()] = (*value).into();
+        }
+        result
+    };
+
+    let mut gained = Vec::new();
+    let mut common = Vec::new();
+    for (perturbation, right_robustness) in &right {
+        if let Some(left_robustness) = left.get(perturbation) {
+            common.push(object! {
+                "perturbation" => to_json(perturbation),
+                "left_robustness" => *left_robustness,
+                "right_robustness" => *right_robustness,
+                "robustness_change" => right_robustness - left_robustness,
+            });
+        } else {
+            gained.push(object! {
+                "perturbation" => to_json(perturbation),
+                "robustness" => *right_robustness,
+            });
+        }
+    }
+    let lost = left
+        .iter()
+        .filter(|(perturbation, _)| !right.contains_key(*perturbation))
+        .map(|(perturbation, robustness)| {
+            object! {
+                "perturbation" => to_json(perturbation),
+                "robustness" => *robustness,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(object! {
+        "gained" => gained,
+        "lost" => lost,
+        "common" => common,
+    })
+}
+
+/// Read the perturbations and their robustness from a control result export. For restricted
+/// exports, the restricted robustness is used.
+fn read_export(
+    export: &JsonValue,
+    field: &str,
+) -> Result<BTreeMap<BTreeMap<String, bool>, f64>, String> {
+    if !export["results"].is_array() {
+        return Err(format!(
+            "Invalid `{field}`: expected exported control results."
+        ));
+    }
+    let key = if export["restricted"].as_bool().unwrap_or(false) {
+        "restricted_robustness"
+    } else {
+        "robustness"
+    };
+    let mut result = BTreeMap::new();
+    for (i, item) in export["results"].members().enumerate() {
+        let mut perturbation = BTreeMap::new();
+        for (var, value) in item["perturbation"].entries() {
+            let Some(value) = value.as_bool() else {
+                return Err(format!(
+                    "Invalid `{field}.results[{i}].perturbation.{var}`: expected a Boolean value."
+                ));
+            };
+            perturbation.insert(var.to_string(), value);
+        }
+        let Some(robustness) = item[key].as_f64() else {
+            return Err(format!(
+                "Invalid `{field}.results[{i}].{key}`: expected a number."
+            ));
+        };
+        result.insert(perturbation, robustness);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::control::{ControlComputation, ControlRequest, compare_control_results};
+    use std::collections::HashMap;
+
+    #[test]
+    fn export_control_csv() {
+        let mut request = json::parse(
+            r#"{ "phenotype": "A", "max_size": 2, "min_robustness": 0, "result_count": 5 }"#,
+        )
+        .unwrap();
+        request["model"] = "A -> B\nB -| A\n".into();
+        let cmp = ControlComputation::new(ControlRequest::from_json(&request).unwrap());
+        let unit = cmp.graph.as_non_perturbable().mk_unit_colors();
+        let results = vec![
+            (HashMap::from([("A".to_string(), true)]), unit.clone()),
+            (
+                HashMap::from([("A".to_string(), true), ("B".to_string(), false)]),
+                unit.clone(),
+            ),
+        ];
+        cmp.update_results(&results);
+
+        let csv = cmp.export_csv();
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "index,size,A,B,color_count,robustness,cost");
+        assert_eq!(lines[1], "0,1,1,,1,1,1");
+        assert_eq!(lines[2], "1,2,1,0,1,1,2");
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn export_restricted_robustness() {
+        let mut request = json::parse(
+            r#"{ "phenotype": "A", "max_size": 1, "min_robustness": 0, "result_count": 5 }"#,
+        )
+        .unwrap();
+        // The update function of `A` is unknown, hence the model has multiple colours.
+        request["model"] = "A -> B\nB -|? A\nA ->? A\n".into();
+        let mut cmp = ControlComputation::new(ControlRequest::from_json(&request).unwrap());
+        let colors = cmp
+            .graph
+            .as_non_perturbable()
+            .mk_unit_colors()
+            .pick_singleton();
+        cmp.restriction = Some(colors.clone());
+        cmp.update_results(&[(HashMap::from([("A".to_string(), true)]), colors)]);
+
+        let csv = cmp.export_csv();
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[0],
+            "index,size,A,color_count,robustness,restricted_robustness,cost"
+        );
+        assert!(lines[1].ends_with(",1,1"));
+
+        let export = cmp.export_json();
+        assert_eq!(export["restricted"], true);
+        assert_eq!(export["results"][0]["restricted_robustness"], 1.0);
+        assert!(export["results"][0]["robustness"].as_f64().unwrap() < 1.0);
+
+        // Restricted exports are compared by their restricted robustness.
+        let diff = compare_control_results(&export, &export).unwrap();
+        assert_eq!(diff["common"][0]["right_robustness"], 1.0);
+        let unrestricted = json::parse(r#"{ "results": [] }"#).unwrap();
+        assert!(compare_control_results(&export, &unrestricted).is_err());
+    }
+
+    #[test]
+    fn compare_control_exports() {
+        let left = json::parse(
+            r#"{ "results": [
+                { "perturbation": { "A": true }, "robustness": 0.5 },
+                { "perturbation": { "B": false }, "robustness": 1.0 }
+            ] }"#,
+        )
+        .unwrap();
+        let right = json::parse(
+            r#"{ "results": [
+                { "perturbation": { "A": true }, "robustness": 0.75 },
+                { "perturbation": { "A": false, "B": true }, "robustness": 0.25 }
+            ] }"#,
+        )
+        .unwrap();
+
+        let diff = compare_control_results(&left, &right).unwrap();
+        assert_eq!(diff["gained"].len(), 1);
+        assert_eq!(diff["gained"][0]["perturbation"]["B"], true);
+        assert_eq!(diff["lost"].len(), 1);
+        assert_eq!(diff["lost"][0]["perturbation"]["B"], false);
+        assert_eq!(diff["common"].len(), 1);
+        assert_eq!(diff["common"][0]["robustness_change"], 0.25);
+
+        let invalid = json::parse(r#"{ "results": [{ "perturbation": { "A": 1 } }] }"#).unwrap();
+        let error = compare_control_results(&left, &invalid).err().unwrap();
+        assert!(error.contains("`right.results[0].perturbation.A`"));
+    }
+}
@@ -0,0 +1,33 @@
+use crate::control::ControlMode;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+impl ControlMode {
+    /// True if the mode drives the network from a specific source state.
+    pub fn requires_source(&self) -> bool {
+        !matches!(self, ControlMode::Permanent)
+    }
+}
+
+impl Display for ControlMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ControlMode::Permanent => write!(f, "permanent"),
+            ControlMode::Temporary => write!(f, "temporary"),
+            ControlMode::Reprogramming => write!(f, "reprogramming"),
+        }
+    }
+}
+
+impl TryFrom<&str> for ControlMode {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "permanent" => Ok(ControlMode::Permanent),
+            "temporary" => Ok(ControlMode::Temporary),
+            "reprogramming" => Ok(ControlMode::Reprogramming),
+            _ => Err(format!("Invalid control mode `{}`.", value)),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::ComputationStatus;
+use crate::control::{ControlComputation, ControlProgress};
+use json::JsonValue;
+use std::sync::atomic::Ordering;
+
+impl ControlProgress {
+    /// Record that all perturbations of the next size were processed, `candidates`
+    /// of which were not yet counted by `evaluate_candidate`.
+    pub fn finish_size(&self, candidates: u64) {
+        self.candidates_evaluated
+            .fetch_add(candidates, Ordering::SeqCst);
+        self.sizes_processed.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Record one evaluated candidate perturbation.
+    pub fn evaluate_candidate(&self) {
+        self.candidates_evaluated.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn set_best_robustness(&self, robustness: Option<f64>) {
+        *self.best_robustness.lock().unwrap() = robustness;
+    }
+
+    pub fn best_robustness(&self) -> Option<f64> {
+        *self.best_robustness.lock().unwrap()
+    }
+}
+
+impl ControlComputation {
+    /// Structured progress of the search, in the same form for all control modes.
+    pub fn progress_json(&self) -> JsonValue {
+        let progress = &self.search_progress;
+        object! {
+            "sizesProcessed" => progress.sizes_processed.load(Ordering::SeqCst),
+            "maxSize" => self.max_size,
+            "candidatesEvaluated" => progress.candidates_evaluated.load(Ordering::SeqCst),
+            "bestRobustness" => progress.best_robustness(),
+        }
+    }
+
+    /// Status of the computation, in the same form as other computation types. The
+    /// `details` contain the structured search progress (see `progress_json`).
+    pub fn status(&self) -> ComputationStatus {
+        ComputationStatus {
+            running: self.thread.is_some(),
+            cancelled: self.is_cancelled.is_cancelled(),
+            elapsed: self.elapsed_millis(),
+            progress: self.progress(),
+            results_found: self.results().len(),
+            details: self.progress_json(),
+        }
+    }
+}
+
+/// Number of perturbations of exactly `size` variables, where `values[i]` is the number of
+/// values the `i`-th variable can be perturbed to. Saturates at `u64::MAX`.
+pub(crate) fn perturbation_count(values: &[u64], size: usize) -> u64 {
+    // `counts[k]` is the number of perturbations of size `k` using the variables seen so far.
+    let mut counts = vec![0u64; size + 1];
+    counts[0] = 1;
+    for value_count in values {
+        for k in (1..=size).rev() {
+            let extended = counts[k - 1].saturating_mul(*value_count);
+            counts[k] = counts[k].saturating_add(extended);
+        }
+    }
+    counts[size]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::control::_impl_control_progress::perturbation_count;
+
+    #[test]
+    fn count_perturbations() {
+        assert_eq!(perturbation_count(&[2, 2, 2], 0), 1);
+        assert_eq!(perturbation_count(&[2, 2, 2], 2), 12);
+        assert_eq!(perturbation_count(&[2, 2], 3), 0);
+        assert_eq!(perturbation_count(&[2; 200], 100), u64::MAX);
+        // One variable with a single allowed value.
+        assert_eq!(perturbation_count(&[2, 1, 2], 2), 8);
+    }
+}
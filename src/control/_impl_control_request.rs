@@ -0,0 +1,615 @@
+use crate::control::{ColorRestriction, ControlMode, ControlRequest, PhenotypeQuantifier};
+use biodivine_lib_param_bn::{BooleanNetwork, FnUpdate, ModelAnnotation, VariableId};
+use biodivine_pbn_control::control::PhenotypeOscillationType;
+use json::JsonValue;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+impl ControlRequest {
+    /// Parse and validate a control request of the following form:
+    ///
+    /// ```json
+    /// {
+    ///   "model": "<aeon model>",
+    ///   "mode": "permanent",
+    ///   "controllable": ["A", "B"],
+    ///   "phenotype": [{ "C": true }, { "C": false, "D": true }],
+    ///   "quantifier": "all",
+    ///   "source": { "A": true, "B": false, "C": false, "D": false },
+    ///   "oscillation": "allowed",
+    ///   "max_size": 2,
+    ///   "min_robustness": 0.5,
+    ///   "result_count": 10,
+    ///   "costs": { "A": 2.5 },
+    ///   "allowed_values": { "B": [false] },
+    ///   "max_cost": 3,
+    ///   "restriction": { "node_id": 4 }
+    /// }
+    /// ```
+    ///
+    /// The `mode` (default `permanent`), `controllable` (default all variables), `source`
+    /// (a single state, only for `temporary` and `reprogramming` mode) and `oscillation` (default `allowed`)
+    /// are optional. The `phenotype` is either a Boolean expression over network variables
+    /// (e.g. `"(A & !B) | C"`), one subspace, or a list of alternative subspaces. The `quantifier`
+    /// (`all` by default, or `any`) says whether all or at least one attractor must satisfy it.
+    /// The perturbation `costs` (default 1 per variable), `allowed_values` of perturbed variables
+    /// (default both) and `max_cost` (default unlimited) are optional as well. Finally,
+    /// the `restriction` to a decision tree node (`node_id`) or behaviour class (`class`)
+    /// is optional.
+    ///
+    /// Errors always name the offending field.
+    pub fn from_json(request: &JsonValue) -> Result<ControlRequest, String> {
+        if !request.is_object() {
+            return Err("Control request must be a JSON object.".to_string());
+        }
+
+        let Some(model) = request["model"].as_str() else {
+            return Err(field_error("model", "expected a model string"));
+        };
+        let network = BooleanNetwork::try_from(model).map_err(|e| field_error("model", &e))?;
+
+        let mode = match &request["mode"] {
+            JsonValue::Null => ControlMode::Permanent,
+            value => {
+                let value = value
+                    .as_str()
+                    .ok_or_else(|| field_error("mode", "expected a string"))?;
+                ControlMode::try_from(value).map_err(|_| {
+                    field_error(
+                        "mode",
+                        "expected `permanent`, `temporary` or `reprogramming`",
+                    )
+                })?
+            }
+        };
+
+        let controllable = match &request["controllable"] {
+            JsonValue::Null => network.variables().collect(),
+            JsonValue::Array(names) => {
+                let mut controllable = Vec::new();
+                for (i, name) in names.iter().enumerate() {
+                    let field = format!("controllable[{i}]");
+                    let Some(name) = name.as_str() else {
+                        return Err(field_error(&field, "expected a variable name"));
+                    };
+                    controllable.push(find_variable(&network, &field, name)?);
+                }
+                controllable
+            }
+            _ => {
+                return Err(field_error(
+                    "controllable",
+                    "expected an array of variable names",
+                ));
+            }
+        };
+
+        let phenotype_expression = match &request["phenotype"] {
+            JsonValue::Array(spaces) => {
+                let mut phenotype = Vec::new();
+                for (i, space) in spaces.iter().enumerate() {
+                    let field = format!("phenotype[{i}]");
+                    phenotype.push(parse_subspace(&network, &field, space)?);
+                }
+                if phenotype.is_empty() {
+                    return Err(field_error(
+                        "phenotype",
+                        "at least one subspace is required",
+                    ));
+                }
+                subspaces_to_expression(&network, &phenotype)
+            }
+            JsonValue::Object(_) => {
+                let space = parse_subspace(&network, "phenotype", &request["phenotype"])?;
+                subspaces_to_expression(&network, &[space])
+            }
+            value => match value.as_str() {
+                Some(expression) => expression.to_string(),
+                None => {
+                    return Err(field_error(
+                        "phenotype",
+                        "expected a Boolean expression, a subspace object or an array of subspaces",
+                    ));
+                }
+            },
+        };
+        let phenotype = parse_phenotype(&network, &phenotype_expression)?;
+
+        let quantifier = match &request["quantifier"] {
+            JsonValue::Null => PhenotypeQuantifier::All,
+            value => {
+                let value = value
+                    .as_str()
+                    .ok_or_else(|| field_error("quantifier", "expected a string"))?;
+                PhenotypeQuantifier::try_from(value)
+                    .map_err(|_| field_error("quantifier", "expected `all` or `any`"))?
+            }
+        };
+
+        let source = match &request["source"] {
+            JsonValue::Null => None,
+            value => Some(parse_subspace(&network, "source", value)?),
+        };
+
+        let oscillation = match &request["oscillation"] {
+            JsonValue::Null => PhenotypeOscillationType::Allowed,
+            value => {
+                let value = value
+                    .as_str()
+                    .ok_or_else(|| field_error("oscillation", "expected a string"))?;
+                parse_oscillation(value)?
+            }
+        };
+
+        let Some(max_size) = request["max_size"].as_usize() else {
+            return Err(field_error("max_size", "expected a non-negative integer"));
+        };
+        let Some(min_robustness) = request["min_robustness"].as_f64() else {
+            return Err(field_error("min_robustness", "expected a number"));
+        };
+        let Some(result_count) = request["result_count"].as_usize() else {
+            return Err(field_error("result_count", "expected a positive integer"));
+        };
+
+        let mut costs = HashMap::new();
+        match &request["costs"] {
+            JsonValue::Null => (),
+            JsonValue::Object(_) => {
+                for (name, cost) in request["costs"].entries() {
+                    let field = format!("costs.{name}");
+                    let var = find_variable(&network, &field, name)?;
+                    let Some(cost) = cost.as_f64() else {
+                        return Err(field_error(&field, "expected a number"));
+                    };
+                    costs.insert(var, cost);
+                }
+            }
+            _ => {
+                return Err(field_error("costs", "expected an object of variable costs"));
+            }
+        }
+
+        let mut allowed_values = HashMap::new();
+        match &request["allowed_values"] {
+            JsonValue::Null => (),
+            JsonValue::Object(_) => {
+                for (name, values) in request["allowed_values"].entries() {
+                    let field = format!("allowed_values.{name}");
+                    let var = find_variable(&network, &field, name)?;
+                    let values = values
+                        .members()
+                        .map(|it| it.as_bool())
+                        .collect::<Option<Vec<_>>>();
+                    let Some(mut values) = values else {
+                        return Err(field_error(&field, "expected an array of Boolean values"));
+                    };
+                    values.sort();
+                    values.dedup();
+                    allowed_values.insert(var, values);
+                }
+            }
+            _ => {
+                return Err(field_error(
+                    "allowed_values",
+                    "expected an object of allowed variable values",
+                ));
+            }
+        }
+
+        let max_cost = match &request["max_cost"] {
+            JsonValue::Null => None,
+            value => Some(
+                value
+                    .as_f64()
+                    .ok_or_else(|| field_error("max_cost", "expected a number"))?,
+            ),
+        };
+
+        let restriction = match &request["restriction"] {
+            JsonValue::Null => None,
+            value => {
+                let node_id = &value["node_id"];
+                let class = &value["class"];
+                let node_id = node_id
+                    .as_str()
+                    .map(|it| it.to_string())
+                    .or_else(|| node_id.as_usize().map(|it| it.to_string()));
+                match (node_id, class.as_str()) {
+                    (Some(node_id), None) => Some(ColorRestriction::TreeNode(node_id)),
+                    (None, Some(class)) => Some(ColorRestriction::Class(class.to_string())),
+                    _ => {
+                        return Err(field_error(
+                            "restriction",
+                            "expected an object with either `node_id` or `class`",
+                        ));
+                    }
+                }
+            }
+        };
+
+        let request = ControlRequest {
+            model: model.to_string(),
+            network,
+            mode,
+            controllable,
+            phenotype,
+            phenotype_expression,
+            quantifier,
+            source,
+            oscillation,
+            max_size,
+            min_robustness,
+            result_count,
+            costs,
+            allowed_values,
+            max_cost,
+            restriction,
+        };
+        request.validate()?;
+        Ok(request)
+    }
+
+    /// Create a control request from `#!control:<var>:<controllable>,<phenotype>`
+    /// and `#!control_source:<var>:<value>` model annotations. The remaining configuration
+    /// is given explicitly.
+    pub fn from_annotations(
+        model: String,
+        mode: &str,
+        quantifier: &str,
+        oscillation: &str,
+        min_robustness: f64,
+        max_size: usize,
+        result_count: usize,
+    ) -> Result<ControlRequest, String> {
+        let network = BooleanNetwork::try_from(model.as_str())?;
+        let annotations = ModelAnnotation::from_model_string(model.as_str());
+
+        let mut controllable = Vec::new();
+        let mut phenotype = Vec::new();
+        let mut source = Vec::new();
+        for var in network.variables() {
+            let name = network.get_variable_name(var);
+            if let Some(value) = annotations.get_value(&["control", name.as_str()]) {
+                let invalid = || format!("Invalid control annotation for variable {name}: {value}");
+                let mut vals = value.split(",");
+                let is_controllable = vals.next();
+                let phenotype_value = vals.next();
+                if vals.next().is_some() {
+                    return Err(invalid());
+                }
+                match is_controllable {
+                    Some("true") => controllable.push(var),
+                    Some("false") => (),
+                    _ => return Err(invalid()),
+                }
+                match phenotype_value {
+                    Some("true") => phenotype.push((var, true)),
+                    Some("false") => phenotype.push((var, false)),
+                    Some("null") => (),
+                    _ => return Err(invalid()),
+                }
+            }
+            match annotations.get_value(&["control_source", name.as_str()]) {
+                Some(value) if value == "true" => source.push((var, true)),
+                Some(value) if value == "false" => source.push((var, false)),
+                Some(value) => {
+                    return Err(format!(
+                        "Invalid control source annotation for variable {name}: {value}"
+                    ));
+                }
+                None => (),
+            }
+        }
+
+        if phenotype.is_empty() {
+            return Err(field_error(
+                "phenotype",
+                "no `#!control` annotation fixes a phenotype value",
+            ));
+        }
+
+        let mode = ControlMode::try_from(mode)?;
+        let phenotype_expression = subspaces_to_expression(&network, &[phenotype]);
+        let request = ControlRequest {
+            mode,
+            controllable,
+            phenotype: parse_phenotype(&network, &phenotype_expression)?,
+            phenotype_expression,
+            quantifier: PhenotypeQuantifier::try_from(quantifier)?,
+            model,
+            network,
+            source: if source.is_empty() {
+                None
+            } else {
+                Some(source)
+            },
+            oscillation: parse_oscillation(oscillation)?,
+            max_size,
+            min_robustness,
+            result_count,
+            costs: HashMap::new(),
+            allowed_values: HashMap::new(),
+            max_cost: None,
+            restriction: None,
+        };
+        request.validate()?;
+        Ok(request)
+    }
+
+    /// Names of the controllable variables.
+    pub fn controllable_names(&self) -> Vec<String> {
+        self.controllable
+            .iter()
+            .map(|var| self.network.get_variable_name(*var).clone())
+            .collect()
+    }
+
+    /// Check the constraints between individual fields of the request.
+    fn validate(&self) -> Result<(), String> {
+        if self.controllable.is_empty() {
+            return Err(field_error(
+                "controllable",
+                "at least one variable is required",
+            ));
+        }
+        for (i, var) in self.controllable.iter().enumerate() {
+            if self.controllable[..i].contains(var) {
+                let name = self.network.get_variable_name(*var);
+                let message = format!("variable `{name}` is listed more than once");
+                return Err(field_error("controllable", &message));
+            }
+        }
+        if self.result_count == 0 {
+            return Err(field_error("result_count", "must be positive"));
+        }
+        if !(0.0..=1.0).contains(&self.min_robustness) {
+            return Err(field_error("min_robustness", "must be between 0 and 1"));
+        }
+        if self.mode.requires_source() {
+            let Some(source) = &self.source else {
+                let message = format!("required by `{}` control", self.mode);
+                return Err(field_error("source", &message));
+            };
+            if let Some(var) = self
+                .network
+                .variables()
+                .find(|var| source.iter().all(|(s_var, _)| s_var != var))
+            {
+                let name = self.network.get_variable_name(var);
+                let message = format!("must be a single state, but `{name}` is not fixed");
+                return Err(field_error("source", &message));
+            }
+        } else if self.source.is_some() {
+            let message = format!("not supported by `{}` control", self.mode);
+            return Err(field_error("source", &message));
+        }
+        for (var, cost) in &self.costs {
+            let field = format!("costs.{}", self.network.get_variable_name(*var));
+            if !self.controllable.contains(var) {
+                return Err(field_error(&field, "variable is not controllable"));
+            }
+            if !cost.is_finite() || *cost < 0.0 {
+                return Err(field_error(&field, "must be a non-negative number"));
+            }
+        }
+        for var in self.allowed_values.keys() {
+            let field = format!("allowed_values.{}", self.network.get_variable_name(*var));
+            if !self.controllable.contains(var) {
+                return Err(field_error(&field, "variable is not controllable"));
+            }
+        }
+        if self.max_cost.is_some_and(|it| it < 0.0) {
+            return Err(field_error("max_cost", "must be non-negative"));
+        }
+        Ok(())
+    }
+}
+
+fn field_error(field: &str, message: &str) -> String {
+    format!("Invalid `{field}`: {message}.")
+}
+
+fn find_variable(network: &BooleanNetwork, field: &str, name: &str) -> Result<VariableId, String> {
+    network
+        .as_graph()
+        .find_variable(name)
+        .ok_or_else(|| field_error(field, &format!("unknown variable `{name}`")))
+}
+
+/// Parse a subspace given as a JSON object mapping variable names to Boolean values.
+pub fn parse_subspace(
+    network: &BooleanNetwork,
+    field: &str,
+    value: &JsonValue,
+) -> Result<Vec<(VariableId, bool)>, String> {
+    if !value.is_object() {
+        return Err(field_error(field, "expected an object of variable values"));
+    }
+    let mut result = Vec::new();
+    for (name, value) in value.entries() {
+        let field = format!("{field}.{name}");
+        let var = find_variable(network, &field, name)?;
+        let Some(value) = value.as_bool() else {
+            return Err(field_error(&field, "expected a Boolean value"));
+        };
+        result.push((var, value));
+    }
+    Ok(result)
+}
+
+/// Write a union of subspaces as a Boolean expression in disjunctive normal form.
+fn subspaces_to_expression(network: &BooleanNetwork, spaces: &[Vec<(VariableId, bool)>]) -> String {
+    spaces
+        .iter()
+        .map(|space| {
+            let literals = space
+                .iter()
+                .map(|(var, value)| {
+                    let name = network.get_variable_name(*var);
+                    if *value {
+                        name.clone()
+                    } else {
+                        format!("!{name}")
+                    }
+                })
+                .collect::<Vec<_>>();
+            if literals.is_empty() {
+                "(true)".to_string()
+            } else {
+                format!("({})", literals.join(" & "))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// Parse a phenotype expression. The expression can only use network variables.
+fn parse_phenotype(network: &BooleanNetwork, expression: &str) -> Result<FnUpdate, String> {
+    let phenotype =
+        FnUpdate::try_from_str(expression, network).map_err(|e| field_error("phenotype", &e))?;
+    if has_parameters(&phenotype) {
+        return Err(field_error(
+            "phenotype",
+            "the expression cannot use parameters",
+        ));
+    }
+    Ok(phenotype)
+}
+
+fn has_parameters(function: &FnUpdate) -> bool {
+    match function {
+        FnUpdate::Const(_) | FnUpdate::Var(_) => false,
+        FnUpdate::Param(_, _) => true,
+        FnUpdate::Not(inner) => has_parameters(inner),
+        FnUpdate::Binary(_, left, right) => has_parameters(left) || has_parameters(right),
+    }
+}
+
+fn parse_oscillation(value: &str) -> Result<PhenotypeOscillationType, String> {
+    match value {
+        "required" => Ok(PhenotypeOscillationType::Required),
+        "forbidden" => Ok(PhenotypeOscillationType::Forbidden),
+        "allowed" => Ok(PhenotypeOscillationType::Allowed),
+        _ => Err(field_error(
+            "oscillation",
+            "expected `required`, `forbidden` or `allowed`",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::control::{ColorRestriction, ControlMode, ControlRequest, PhenotypeQuantifier};
+
+    const MODEL: &str = "A -> B\nB -| A\n";
+
+    fn parse(request: &str) -> Result<ControlRequest, String> {
+        let mut request = json::parse(request).unwrap();
+        request["model"] = MODEL.into();
+        ControlRequest::from_json(&request)
+    }
+
+    #[test]
+    fn control_request_from_json() {
+        let request = parse(
+            r#"{ "phenotype": [{ "A": true }, { "B": false }], "max_size": 1,
+                 "min_robustness": 0.5, "result_count": 3 }"#,
+        )
+        .unwrap();
+        assert_eq!(request.mode, ControlMode::Permanent);
+        assert_eq!(request.controllable.len(), 2);
+        assert_eq!(request.phenotype_expression, "(A) | (!B)");
+        assert_eq!(request.quantifier, PhenotypeQuantifier::All);
+        assert_eq!(request.result_count, 3);
+        assert_eq!(request.restriction, None);
+
+        let request = parse(
+            r#"{ "phenotype": "A & (B | !A)", "quantifier": "any", "max_size": 1,
+                 "min_robustness": 0.5, "result_count": 3 }"#,
+        )
+        .unwrap();
+        assert_eq!(request.phenotype_expression, "A & (B | !A)");
+        assert_eq!(request.quantifier, PhenotypeQuantifier::Any);
+
+        let request = parse(
+            r#"{ "mode": "reprogramming", "controllable": ["A"], "phenotype": { "A": true },
+                 "source": { "A": false, "B": true }, "max_size": 1, "min_robustness": 0,
+                 "result_count": 1, "restriction": { "node_id": 3 } }"#,
+        )
+        .unwrap();
+        assert_eq!(request.mode, ControlMode::Reprogramming);
+        assert_eq!(request.controllable.len(), 1);
+        assert!(request.source.is_some());
+        assert_eq!(
+            request.restriction,
+            Some(ColorRestriction::TreeNode("3".to_string()))
+        );
+    }
+
+    #[test]
+    fn control_request_errors() {
+        let base = r#""max_size": 1, "min_robustness": 0.5, "result_count": 3"#;
+        let error = |fields: &str| parse(&format!("{{ {fields}, {base} }}")).err().unwrap();
+
+        assert!(error(r#""phenotype": { "X": true }"#).contains("`phenotype.X`"));
+        assert!(error(r#""phenotype": [{}, { "A": 1 }]"#).contains("`phenotype[1].A`"));
+        assert!(error(r#""phenotype": {}, "controllable": [1]"#).contains("`controllable[0]`"));
+        assert!(error(r#""phenotype": {}, "mode": "random""#).contains("`mode`"));
+        assert!(error(r#""phenotype": {}, "mode": "temporary""#).contains("`source`"));
+        assert!(error(r#""phenotype": {}, "source": {}"#).contains("`source`"));
+        let fields = r#""phenotype": {}, "mode": "temporary", "source": { "A": true }"#;
+        assert!(error(fields).contains("`B` is not fixed"));
+        assert!(error(r#""phenotype": {}, "oscillation": "never""#).contains("`oscillation`"));
+        assert!(error(r#""phenotype": "A & X""#).contains("`phenotype`"));
+        assert!(error(r#""phenotype": "A &""#).contains("`phenotype`"));
+        assert!(error(r#""phenotype": 1"#).contains("`phenotype`"));
+        assert!(error(r#""phenotype": []"#).contains("`phenotype`"));
+        assert!(error(r#""phenotype": "A", "quantifier": "some""#).contains("`quantifier`"));
+
+        let error =
+            parse(r#"{ "phenotype": {}, "max_size": 1, "min_robustness": 2, "result_count": 3 }"#);
+        assert!(error.err().unwrap().contains("`min_robustness`"));
+        let error =
+            parse(r#"{ "phenotype": {}, "max_size": 1, "min_robustness": 0, "result_count": 0 }"#);
+        assert!(error.err().unwrap().contains("`result_count`"));
+        let error = parse(r#"{ "phenotype": {}, "min_robustness": 0, "result_count": 1 }"#);
+        assert!(error.err().unwrap().contains("`max_size`"));
+
+        assert!(error(r#""phenotype": {}, "costs": { "A": -1 }"#).contains("`costs.A`"));
+        assert!(error(r#""phenotype": {}, "costs": { "A": true }"#).contains("`costs.A`"));
+        let fields = r#""phenotype": {}, "controllable": ["A"], "allowed_values": { "B": [true] }"#;
+        assert!(error(fields).contains("`allowed_values.B`"));
+        let fields = r#""phenotype": {}, "allowed_values": { "B": ["true"] }"#;
+        assert!(error(fields).contains("`allowed_values.B`"));
+        let fields = r#""phenotype": {}, "restriction": { "node_id": 1, "class": "[]" }"#;
+        assert!(error(fields).contains("`restriction`"));
+        assert!(error(r#""phenotype": {}, "controllable": []"#).contains("`controllable`"));
+        let fields = r#""phenotype": {}, "controllable": ["A", "B", "A"]"#;
+        assert!(error(fields).contains("`A` is listed more than once"));
+    }
+
+    #[test]
+    fn control_request_from_annotations() {
+        let annotated = format!("{MODEL}#!control:A:true,null\n#!control:B:false,true\n");
+        let request =
+            ControlRequest::from_annotations(annotated, "permanent", "all", "allowed", 0.5, 1, 3)
+                .unwrap();
+        assert_eq!(request.controllable_names(), vec!["A".to_string()]);
+        assert_eq!(request.phenotype_expression, "(B)");
+
+        let error = ControlRequest::from_annotations(
+            MODEL.to_string(),
+            "permanent",
+            "all",
+            "allowed",
+            0.5,
+            1,
+            3,
+        );
+        assert!(error.err().unwrap().contains("`phenotype`"));
+        let annotated = format!("{MODEL}#!control:B:false,true\n");
+        let error =
+            ControlRequest::from_annotations(annotated, "permanent", "all", "allowed", 0.5, 1, 3);
+        assert!(error.err().unwrap().contains("`controllable`"));
+    }
+}
@@ -0,0 +1,408 @@
+use crate::GraphTaskContext;
+use crate::control::{ControlComputation, ControlMode, ControlResult, PhenotypeQuantifier};
+use crate::scc::algo_reachability::{backward_reach, forward_reach};
+use biodivine_lib_param_bn::biodivine_std::bitvector::BitVector;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{
+    GraphColoredVertices, GraphColors, SymbolicAsyncGraph,
+};
+use biodivine_lib_param_bn::{FnUpdate, VariableId};
+use biodivine_pbn_control::control::PhenotypeOscillationType;
+use std::collections::HashMap;
+
+/// The states (of all colours) of `graph` which satisfy the `phenotype` expression.
+pub(crate) fn phenotype_vertices(
+    graph: &SymbolicAsyncGraph,
+    phenotype: &FnUpdate,
+) -> GraphColoredVertices {
+    let phenotype = graph.symbolic_context().mk_fn_update_true(phenotype);
+    let unit = graph.mk_unit_colored_vertices();
+    unit.copy(unit.as_bdd().and(&phenotype))
+}
+
+impl ControlComputation {
+    /// Control by explicit enumeration of candidate perturbations. Each perturbation is
+    /// applied to the non-perturbable graph and the attractors of the perturbed network
+    /// (reachable from the source state, if given) are checked against the phenotype.
+    ///
+    /// This is used for all combinations of control mode and quantifier which are not
+    /// supported by the symbolic algorithms of `pbn-control`. Results are streamed into
+    /// `results` after each perturbation size.
+    pub(super) fn run_explicit(&self) -> Vec<ControlResult> {
+        let graph = self.graph.as_non_perturbable();
+        let phenotype = phenotype_vertices(graph, &self.phenotype);
+        let task = GraphTaskContext::new();
+
+        let mut found = Vec::new();
+        for size in 0..=self.size_limit() {
+            let is_completed = self.for_each_perturbation(size, &mut |perturbation| {
+                if self.is_cancelled.is_cancelled() {
+                    return false;
+                }
+                let names = perturbation
+                    .iter()
+                    .map(|(var, value)| (graph.get_variable_name(*var).clone(), *value))
+                    .collect::<HashMap<_, _>>();
+                if !self.is_allowed(&names) {
+                    return true;
+                }
+                self.search_progress.evaluate_candidate();
+                let Some(colors) = self.controlled_colors(graph, &phenotype, perturbation, &task)
+                else {
+                    // Cancelled while computing reachability.
+                    return false;
+                };
+                if !colors.is_empty() {
+                    found.push((names, colors));
+                }
+                true
+            });
+            if !is_completed {
+                return found;
+            }
+            let is_full = self.update_results(&found);
+            self.search_progress.finish_size(0);
+            if is_full {
+                break;
+            }
+        }
+        found
+    }
+
+    /// Call `action` for all perturbations of exactly `size` controllable variables, using
+    /// only the allowed values of each variable. The perturbations are generated lazily and
+    /// the enumeration stops once `action` returns `false`.
+    ///
+    /// Returns `false` if the enumeration was stopped early.
+    fn for_each_perturbation<F>(&self, size: usize, action: &mut F) -> bool
+    where
+        F: FnMut(&[(VariableId, bool)]) -> bool,
+    {
+        let graph = self.graph.as_non_perturbable();
+        let values = |var: VariableId| {
+            self.allowed_values
+                .get(graph.get_variable_name(var))
+                .cloned()
+                .unwrap_or_else(|| vec![true, false])
+        };
+        let mut current = Vec::with_capacity(size);
+        extend_perturbations(&self.controllable, size, &values, &mut current, action)
+    }
+
+    /// The colours for which the `perturbation` drives the network into the phenotype.
+    /// Returns `None` if the computation is cancelled.
+    fn controlled_colors(
+        &self,
+        graph: &SymbolicAsyncGraph,
+        phenotype: &GraphColoredVertices,
+        perturbation: &[(VariableId, bool)],
+        task: &GraphTaskContext,
+    ) -> Option<GraphColors> {
+        // Temporary perturbations are only applied to the source state. Otherwise, the
+        // perturbed variables are fixed and their transitions are ignored.
+        let (space, free_vars) = match self.mode {
+            ControlMode::Temporary => (
+                graph.mk_unit_colored_vertices(),
+                graph.variables().collect::<Vec<_>>(),
+            ),
+            ControlMode::Permanent | ControlMode::Reprogramming => {
+                let free_vars = graph
+                    .variables()
+                    .filter(|var| perturbation.iter().all(|(p_var, _)| p_var != var))
+                    .collect::<Vec<_>>();
+                (graph.mk_subspace(perturbation), free_vars)
+            }
+        };
+
+        let universe = match self.perturbed_source(perturbation) {
+            Some(source) => forward_reach(graph, &graph.mk_subspace(&source), &space, task)?,
+            None => space,
+        };
+
+        let positive = universe.intersect(phenotype);
+        let negative = universe.minus(phenotype);
+        let trap = |set: &GraphColoredVertices| trap_core(graph, set, &free_vars);
+        let unit = graph.mk_unit_colors();
+
+        let colors = match (self.oscillation, self.quantifier) {
+            (PhenotypeOscillationType::Forbidden, PhenotypeQuantifier::Any) => {
+                trap(&positive).colors()
+            }
+            (PhenotypeOscillationType::Forbidden, PhenotypeQuantifier::All) => {
+                let reach_negative = backward_reach(graph, &negative, &universe, task)?;
+                unit.minus(&trap(&reach_negative).colors())
+            }
+            (PhenotypeOscillationType::Allowed, PhenotypeQuantifier::Any) => {
+                let reach_positive = backward_reach(graph, &positive, &universe, task)?;
+                trap(&reach_positive).colors()
+            }
+            (PhenotypeOscillationType::Allowed, PhenotypeQuantifier::All) => {
+                let reach_positive = backward_reach(graph, &positive, &universe, task)?;
+                unit.minus(&trap(&universe.minus(&reach_positive)).colors())
+            }
+            (PhenotypeOscillationType::Required, PhenotypeQuantifier::Any) => {
+                let reach_positive = backward_reach(graph, &positive, &universe, task)?;
+                let reach_negative = backward_reach(graph, &negative, &universe, task)?;
+                trap(&reach_positive.intersect(&reach_negative)).colors()
+            }
+            (PhenotypeOscillationType::Required, PhenotypeQuantifier::All) => {
+                let reach_positive = backward_reach(graph, &positive, &universe, task)?;
+                let reach_negative = backward_reach(graph, &negative, &universe, task)?;
+                let outside = trap(&universe.minus(&reach_positive))
+                    .union(&trap(&universe.minus(&reach_negative)));
+                unit.minus(&outside.colors())
+            }
+        };
+        Some(colors)
+    }
+
+    /// The source state with the perturbed variables set to their perturbed values,
+    /// as a subspace fixing all variables. Returns `None` if there is no source state.
+    fn perturbed_source(
+        &self,
+        perturbation: &[(VariableId, bool)],
+    ) -> Option<Vec<(VariableId, bool)>> {
+        let mut state = self.pick_state(self.source.as_ref()?)?;
+        for (var, value) in perturbation {
+            state.set((*var).into(), *value);
+        }
+        let state = self
+            .graph
+            .as_non_perturbable()
+            .variables()
+            .map(|var| (var, state.get(var.into())))
+            .collect();
+        Some(state)
+    }
+}
+
+/// Extend `current` with perturbations of the remaining `candidates` until it contains
+/// `size` variables and call `action` on each complete perturbation. Returns `false` if
+/// `action` stopped the enumeration.
+fn extend_perturbations<V, F>(
+    candidates: &[VariableId],
+    size: usize,
+    values: &V,
+    current: &mut Vec<(VariableId, bool)>,
+    action: &mut F,
+) -> bool
+where
+    V: Fn(VariableId) -> Vec<bool>,
+    F: FnMut(&[(VariableId, bool)]) -> bool,
+{
+    if current.len() == size {
+        return action(current);
+    }
+    let missing = size - current.len();
+    // Only extend with variables that leave enough candidates for the remaining positions.
+    let usable = (candidates.len() + 1).saturating_sub(missing);
+    for (i, var) in candidates.iter().enumerate().take(usable) {
+        for value in values(*var) {
+            current.push((*var, value));
+            let is_completed =
+                extend_perturbations(&candidates[i + 1..], size, values, current, action);
+            current.pop();
+            if !is_completed {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// The largest subset of `set` which is a trap set with respect to the transitions
+/// of the `free_vars` variables.
+fn trap_core(
+    graph: &SymbolicAsyncGraph,
+    set: &GraphColoredVertices,
+    free_vars: &[VariableId],
+) -> GraphColoredVertices {
+    let mut result = set.clone();
+    'trap: loop {
+        for var in free_vars {
+            let can_leave = graph.var_can_post_out(*var, &result);
+            if !can_leave.is_empty() {
+                result = result.minus(&can_leave);
+                continue 'trap;
+            }
+        }
+        return result;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GraphTaskContext;
+    use crate::control::_impl_explicit_control::phenotype_vertices;
+    use crate::control::{ControlComputation, ControlRequest, ControlResult, PhenotypeQuantifier};
+    use biodivine_lib_param_bn::biodivine_std::traits::Set;
+    use biodivine_pbn_control::control::PhenotypeOscillationType;
+    use std::collections::BTreeSet;
+    use std::sync::atomic::Ordering;
+
+    // A toggle switch of `A` and `B`, where `C` oscillates when `A` is active. The attractors
+    // are `A & !B` (with oscillating `C`) and the fixed point `!A & B & !C`.
+    const MODEL: &str = "$A: !B\n$B: !A\n$C: A & !C\nB -| A\nA -| B\nA -> C\nC -| C\n";
+
+    const FIXED_POINT: &str = r#"{ "A": false, "B": true, "C": false }"#;
+    const OSCILLATION: &str = r#"{ "A": true, "B": false, "C": false }"#;
+
+    fn computation(
+        mode: &str,
+        phenotype: &str,
+        source: Option<&str>,
+        max_size: usize,
+    ) -> ControlComputation {
+        let source = source.unwrap_or("null");
+        let request = format!(
+            r#"{{ "mode": "{mode}", "phenotype": "{phenotype}", "source": {source},
+                  "max_size": {max_size}, "min_robustness": 0, "result_count": 100 }}"#
+        );
+        let mut request = json::parse(&request).unwrap();
+        request["model"] = MODEL.into();
+        ControlComputation::new(ControlRequest::from_json(&request).unwrap())
+    }
+
+    /// For each oscillation type (allowed, forbidden, required), whether the perturbation
+    /// controls the network for all and for any attractor.
+    fn controls(cmp: &mut ControlComputation, perturbation: &[(&str, bool)]) -> [bool; 6] {
+        let graph = cmp.graph.as_non_perturbable().clone();
+        let perturbation = perturbation
+            .iter()
+            .map(|(name, value)| (graph.find_variable(name).unwrap(), *value))
+            .collect::<Vec<_>>();
+        let phenotype = phenotype_vertices(&graph, &cmp.phenotype);
+        let task = GraphTaskContext::new();
+        let mut result = [false; 6];
+        let oscillations = [
+            PhenotypeOscillationType::Allowed,
+            PhenotypeOscillationType::Forbidden,
+            PhenotypeOscillationType::Required,
+        ];
+        for (i, oscillation) in oscillations.into_iter().enumerate() {
+            cmp.oscillation = oscillation;
+            for (j, quantifier) in [PhenotypeQuantifier::All, PhenotypeQuantifier::Any]
+                .into_iter()
+                .enumerate()
+            {
+                cmp.quantifier = quantifier;
+                let colors = cmp
+                    .controlled_colors(&graph, &phenotype, &perturbation, &task)
+                    .unwrap();
+                result[2 * i + j] = !colors.is_empty();
+            }
+        }
+        result
+    }
+
+    fn perturbations(results: &[ControlResult]) -> BTreeSet<Vec<(String, bool)>> {
+        results
+            .iter()
+            .filter(|(_, colors)| !colors.is_empty())
+            .map(|(perturbation, _)| {
+                let mut perturbation = perturbation.clone().into_iter().collect::<Vec<_>>();
+                perturbation.sort();
+                perturbation
+            })
+            .collect()
+    }
+
+    #[test]
+    fn explicit_permanent_control() {
+        let mut cmp = computation("permanent", "C | B", None, 1);
+        // Columns: allowed (all, any), forbidden (all, any), required (all, any).
+        let expected = [true, true, false, true, false, true];
+        assert_eq!(controls(&mut cmp, &[]), expected);
+        let expected = [true, true, false, false, true, true];
+        assert_eq!(controls(&mut cmp, &[("A", true)]), expected);
+        let expected = [true, true, true, true, false, false];
+        assert_eq!(controls(&mut cmp, &[("A", false)]), expected);
+        let expected = [false, true, false, true, false, false];
+        assert_eq!(controls(&mut cmp, &[("C", false)]), expected);
+    }
+
+    #[test]
+    fn explicit_temporary_control() {
+        let mut cmp = computation("temporary", "C | B", Some(FIXED_POINT), 2);
+        let expected = [true, true, true, true, false, false];
+        assert_eq!(controls(&mut cmp, &[]), expected);
+        let expected = [true, true, false, true, false, true];
+        assert_eq!(controls(&mut cmp, &[("A", true)]), expected);
+        let expected = [true, true, false, false, true, true];
+        assert_eq!(controls(&mut cmp, &[("A", true), ("B", false)]), expected);
+    }
+
+    #[test]
+    fn explicit_reprogramming_control() {
+        let mut cmp = computation("reprogramming", "C | B", Some(FIXED_POINT), 1);
+        let expected = [true, true, true, true, false, false];
+        assert_eq!(controls(&mut cmp, &[]), expected);
+        let expected = [true, true, false, false, true, true];
+        assert_eq!(controls(&mut cmp, &[("A", true)]), expected);
+        assert_eq!(controls(&mut cmp, &[("B", false)]), expected);
+        // Unlike permanent control, only the attractor reachable from the source matters.
+        let expected = [true, true, true, true, false, false];
+        assert_eq!(controls(&mut cmp, &[("C", false)]), expected);
+    }
+
+    #[test]
+    fn control_stops_early() {
+        // The empty perturbation already works, hence the search stops after size zero.
+        for quantifier in [PhenotypeQuantifier::All, PhenotypeQuantifier::Any] {
+            let mut cmp = computation("permanent", "C | B", None, 1);
+            cmp.quantifier = quantifier;
+            cmp.result_count = 1;
+            cmp.run();
+            assert_eq!(cmp.results().len(), 1);
+            let sizes = cmp.search_progress.sizes_processed.load(Ordering::SeqCst);
+            assert_eq!(sizes, 1);
+        }
+    }
+
+    #[test]
+    fn lazy_perturbation_enumeration() {
+        let mut cmp = computation("permanent", "C", None, 2);
+        cmp.allowed_values.insert("A".to_string(), vec![true]);
+        let mut count = 0;
+        assert!(cmp.for_each_perturbation(2, &mut |_| {
+            count += 1;
+            true
+        }));
+        // `A` has one allowed value: AB (2), AC (2) and BC (4).
+        assert_eq!(count, 8);
+
+        let mut count = 0;
+        assert!(!cmp.for_each_perturbation(2, &mut |_| {
+            count += 1;
+            count < 3
+        }));
+        assert_eq!(count, 3);
+
+        assert!(cmp.for_each_perturbation(4, &mut |_| panic!("No such perturbation.")));
+    }
+
+    #[test]
+    fn explicit_control_matches_library() {
+        let oscillations = [
+            PhenotypeOscillationType::Allowed,
+            PhenotypeOscillationType::Forbidden,
+            PhenotypeOscillationType::Required,
+        ];
+        for oscillation in oscillations {
+            let mut cmp = computation("permanent", "C | B", None, 1);
+            cmp.oscillation = oscillation;
+            let symbolic = perturbations(&cmp.run_permanent());
+            let explicit = perturbations(&cmp.run_explicit());
+            assert_eq!(symbolic, explicit);
+        }
+
+        // Source-target control of the fixed point, starting in the oscillating attractor.
+        for mode in ["temporary", "reprogramming"] {
+            let cmp = computation(mode, "!A & B & !C", Some(OSCILLATION), 2);
+            let target = cmp.phenotype_state().unwrap();
+            let symbolic = perturbations(&cmp.run_attractor(&target));
+            let explicit = perturbations(&cmp.run_explicit());
+            assert_eq!(symbolic, explicit);
+        }
+    }
+}
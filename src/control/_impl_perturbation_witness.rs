@@ -0,0 +1,146 @@
+use crate::GraphTaskContext;
+use crate::control::_impl_explicit_control::phenotype_vertices;
+use crate::control::PerturbationWitness;
+use crate::scc::AttractorSummary;
+use crate::scc::algo_attractor_search::attractor_search;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+use biodivine_lib_param_bn::{BooleanNetwork, FnUpdate, RegulatoryGraph};
+use json::JsonValue;
+use std::collections::HashMap;
+
+impl PerturbationWitness {
+    /// Apply the `perturbation` to the given witness `network` (i.e. make the perturbed
+    /// variables constant) and compute the attractors of the perturbed network.
+    ///
+    /// The `network` must be fully instantiated (it has no parameters). The attractor
+    /// search can be cancelled using the `task`, in which case an error is returned.
+    pub fn new(
+        network: BooleanNetwork,
+        perturbation: &HashMap<String, bool>,
+        task: &GraphTaskContext,
+    ) -> Result<PerturbationWitness, String> {
+        let perturbed = perturbed_network(&network, perturbation)?;
+        let graph = SymbolicAsyncGraph::new(&perturbed)?;
+        task.init_progress(&graph);
+        let mut attractors = Vec::new();
+        attractor_search(&graph, task, |attractor| attractors.push(attractor))
+            .map_err(|_| "Attractor search cancelled.".to_string())?;
+        Ok(PerturbationWitness {
+            network,
+            graph,
+            attractors,
+        })
+    }
+
+    /// Export the witness network and its perturbed attractors. Each attractor is marked
+    /// based on whether it lies in the `phenotype`.
+    pub fn to_json(&self, phenotype: &FnUpdate, samples: usize) -> JsonValue {
+        let phenotype = phenotype_vertices(&self.graph, phenotype);
+        let attractors = self
+            .attractors
+            .iter()
+            .map(|attractor| {
+                let summary = AttractorSummary::compute(&self.graph, attractor, samples);
+                object! {
+                    "is_phenotype" => attractor.is_subset(&phenotype),
+                    "summary" => summary.to_json(&self.graph),
+                }
+            })
+            .collect::<Vec<_>>();
+        object! {
+            "model" => self.network.to_string(),
+            "attractors" => attractors,
+        }
+    }
+}
+
+/// Copy of the `network` where the perturbed variables have constant update functions.
+///
+/// The constant functions do not depend on any regulator, hence the regulations of
+/// the perturbed variables are made non-observable and their monotonicity is dropped.
+/// Otherwise, the perturbed network would violate the regulatory constraints.
+fn perturbed_network(
+    network: &BooleanNetwork,
+    perturbation: &HashMap<String, bool>,
+) -> Result<BooleanNetwork, String> {
+    let mut perturbed_vars = Vec::new();
+    for name in perturbation.keys() {
+        let Some(var) = network.as_graph().find_variable(name) else {
+            return Err(format!("Unknown perturbed variable `{name}`."));
+        };
+        perturbed_vars.push(var);
+    }
+
+    let names = network
+        .variables()
+        .map(|var| network.get_variable_name(var).clone())
+        .collect::<Vec<_>>();
+    let mut graph = RegulatoryGraph::new(names);
+    for regulation in network.as_graph().regulations() {
+        let regulator = network.get_variable_name(regulation.get_regulator());
+        let target = network.get_variable_name(regulation.get_target());
+        if perturbed_vars.contains(&regulation.get_target()) {
+            graph.add_regulation(regulator, target, false, None)?;
+        } else {
+            graph.add_regulation(
+                regulator,
+                target,
+                regulation.is_observable(),
+                regulation.get_monotonicity(),
+            )?;
+        }
+    }
+
+    let mut perturbed = BooleanNetwork::new(graph);
+    for var in network.variables() {
+        let function = match perturbation.get(network.get_variable_name(var)) {
+            Some(value) => Some(FnUpdate::Const(*value)),
+            None => network.get_update_function(var).clone(),
+        };
+        perturbed.set_update_function(var, function)?;
+    }
+    Ok(perturbed)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GraphTaskContext;
+    use crate::control::PerturbationWitness;
+    use biodivine_lib_param_bn::{BooleanNetwork, FnUpdate};
+    use std::collections::HashMap;
+
+    // A toggle switch of `A` and `B`, where `C` oscillates when `A` is active. All
+    // regulations are observable and monotone.
+    const MODEL: &str = "$A: !B\n$B: !A\n$C: A & !C\nB -| A\nA -| B\nA -> C\nC -| C\n";
+
+    #[test]
+    fn perturbation_witness_attractors() {
+        let network = BooleanNetwork::try_from(MODEL).unwrap();
+        let task = GraphTaskContext::new();
+        let witness = PerturbationWitness::new(network.clone(), &HashMap::new(), &task).unwrap();
+        assert_eq!(witness.attractors.len(), 2);
+
+        // `A` has an observable monotone regulator `B`, which is no longer observable once
+        // `A` is constant.
+        let perturbation = HashMap::from([("A".to_string(), true)]);
+        let witness = PerturbationWitness::new(network.clone(), &perturbation, &task).unwrap();
+        assert_eq!(witness.graph.unit_colors().approx_cardinality(), 1.0);
+        assert_eq!(witness.attractors.len(), 1);
+        assert_eq!(witness.attractors[0].approx_cardinality(), 2.0);
+
+        let json = witness.to_json(&FnUpdate::try_from_str("!B", &network).unwrap(), 5);
+        assert_eq!(json["attractors"][0]["is_phenotype"], true);
+        let json = witness.to_json(&FnUpdate::try_from_str("C", &network).unwrap(), 5);
+        assert_eq!(json["attractors"][0]["is_phenotype"], false);
+
+        // Both `A` and `C` are constant, which makes their self-regulation irrelevant as well.
+        let perturbation = HashMap::from([("A".to_string(), false), ("C".to_string(), true)]);
+        let witness = PerturbationWitness::new(network.clone(), &perturbation, &task).unwrap();
+        assert_eq!(witness.attractors.len(), 1);
+        assert_eq!(witness.attractors[0].approx_cardinality(), 1.0);
+
+        let perturbation = HashMap::from([("X".to_string(), true)]);
+        assert!(PerturbationWitness::new(network, &perturbation, &task).is_err());
+    }
+}
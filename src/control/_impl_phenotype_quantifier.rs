@@ -0,0 +1,24 @@
+use crate::control::PhenotypeQuantifier;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+impl Display for PhenotypeQuantifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PhenotypeQuantifier::All => write!(f, "all"),
+            PhenotypeQuantifier::Any => write!(f, "any"),
+        }
+    }
+}
+
+impl TryFrom<&str> for PhenotypeQuantifier {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "all" => Ok(PhenotypeQuantifier::All),
+            "any" => Ok(PhenotypeQuantifier::Any),
+            _ => Err(format!("Invalid phenotype quantifier `{}`.", value)),
+        }
+    }
+}
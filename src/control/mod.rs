@@ -0,0 +1,152 @@
+use biodivine_lib_param_bn::symbolic_async_graph::{
+    GraphColoredVertices, GraphColors, SymbolicAsyncGraph,
+};
+use biodivine_lib_param_bn::{BooleanNetwork, FnUpdate, VariableId};
+use biodivine_pbn_control::control::PhenotypeOscillationType;
+use biodivine_pbn_control::perturbation::PerturbationGraph;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicUsize};
+use std::thread::JoinHandle;
+use std::time::SystemTime;
+
+mod _impl_control_computation;
+mod _impl_control_export;
+mod _impl_control_mode;
+mod _impl_control_progress;
+mod _impl_control_request;
+mod _impl_explicit_control;
+mod _impl_perturbation_witness;
+mod _impl_phenotype_quantifier;
+
+pub use _impl_control_export::compare_control_results;
+pub use _impl_control_request::parse_subspace;
+
+/// A single perturbation (variable name to perturbed value) together with the colors
+/// for which it controls the network.
+pub type ControlResult = (HashMap<String, bool>, GraphColors);
+
+/// Which kind of control problem is being solved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ControlMode {
+    /// Perturbations that are applied indefinitely and drive the network into
+    /// the target phenotype from any state.
+    Permanent,
+    /// Perturbations applied for a single step in the source state (e.g. a drug pulse),
+    /// after which the unperturbed network has to reach the target attractor.
+    Temporary,
+    /// Perturbations applied indefinitely which reprogram the network from the source
+    /// attractor into the target attractor.
+    Reprogramming,
+}
+
+/// Which attractors of the perturbed network must satisfy the phenotype.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PhenotypeQuantifier {
+    /// All (reachable) attractors must satisfy the phenotype.
+    All,
+    /// At least one (reachable) attractor must satisfy the phenotype.
+    Any,
+}
+
+/// A colour restriction given by the results of an attractor computation
+/// in the same session.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ColorRestriction {
+    /// Colours of a bifurcation decision tree node, given by its id.
+    TreeNode(String),
+    /// Colours of a behaviour class, in the format accepted by `Class::try_read_string`.
+    Class(String),
+}
+
+/// A validated description of a control problem, created either from a JSON request
+/// (`ControlRequest::from_json`) or from `#!control` model annotations
+/// (`ControlRequest::from_annotations`).
+pub struct ControlRequest {
+    pub model: String,
+    pub network: BooleanNetwork,
+    pub mode: ControlMode,
+    /// Variables that can be perturbed.
+    pub controllable: Vec<VariableId>,
+    /// The phenotype as a Boolean expression over network variables (without parameters).
+    pub phenotype: FnUpdate,
+    /// The phenotype expression as given by the user (or derived from phenotype subspaces).
+    pub phenotype_expression: String,
+    pub quantifier: PhenotypeQuantifier,
+    /// The source state of `Temporary` and `Reprogramming` control.
+    pub source: Option<Vec<(VariableId, bool)>>,
+    pub oscillation: PhenotypeOscillationType,
+    pub max_size: usize,
+    pub min_robustness: f64,
+    pub result_count: usize,
+    /// Cost of perturbing each variable. Variables without explicit cost have cost `1.0`.
+    pub costs: HashMap<VariableId, f64>,
+    /// Values to which a variable can be perturbed. Variables without explicit entry
+    /// can be perturbed to both values.
+    pub allowed_values: HashMap<VariableId, Vec<bool>>,
+    /// Only perturbations with total cost at most this value are reported.
+    pub max_cost: Option<f64>,
+    /// Colours within which the robustness is (also) measured.
+    pub restriction: Option<ColorRestriction>,
+}
+
+/// Progress of the control search, updated by the computation thread while it runs.
+#[derive(Default)]
+pub struct ControlProgress {
+    /// Number of perturbation sizes (starting from zero) that are fully processed.
+    pub sizes_processed: AtomicUsize,
+    /// Number of candidate perturbations evaluated so far.
+    pub candidates_evaluated: AtomicU64,
+    /// The best (effective) robustness among the current results.
+    pub best_robustness: Mutex<Option<f64>>,
+}
+
+pub struct ControlComputation {
+    pub timestamp: SystemTime,
+    pub finished_timestamp: Option<SystemTime>,
+    pub input_model: String,
+    pub graph: PerturbationGraph,
+    pub thread: Option<JoinHandle<()>>,
+    pub mode: ControlMode,
+    /// The source state of `Temporary` and `Reprogramming` control, given as a subspace.
+    pub source: Option<Vec<(VariableId, bool)>>,
+    /// Variables that can be perturbed.
+    pub controllable: Vec<VariableId>,
+    /// The phenotype expression (see `ControlRequest::phenotype`).
+    pub phenotype: FnUpdate,
+    pub phenotype_expression: String,
+    pub quantifier: PhenotypeQuantifier,
+    /// Perturbations discovered so far. The computation thread only holds a read lock
+    /// on the computation, hence the results are updated through this mutex while
+    /// the computation is still running.
+    pub results: Mutex<Vec<ControlResult>>,
+    pub oscillation: PhenotypeOscillationType,
+    /// Only perturbations with at most this many perturbed variables are considered.
+    pub max_size: usize,
+    /// Only perturbations with robustness at least this value are reported.
+    pub min_robustness: f64,
+    /// The computation stops once this many perturbations are found.
+    pub result_count: usize,
+    /// Perturbation costs and constraints, indexed by variable name
+    /// (see `ControlRequest` for details).
+    pub costs: HashMap<String, f64>,
+    pub allowed_values: HashMap<String, Vec<bool>>,
+    pub max_cost: Option<f64>,
+    /// If set, robustness is (also) measured relative to these colours. The `min_robustness`
+    /// threshold and ranking of results then use the restricted robustness.
+    pub restriction: Option<GraphColors>,
+    /// Effective robustness of already ranked results (with sorted perturbation as key), so
+    /// that the cardinality of each result is only computed once while results are streamed.
+    pub robustness_cache: Mutex<HashMap<Vec<(String, bool)>, (GraphColors, f64)>>,
+    pub search_progress: ControlProgress,
+    pub is_cancelled: cancel_this::CancelAtomic,
+}
+
+/// One witness parametrization of a perturbation: the (unperturbed) witness network,
+/// together with the attractors of the witness network after the perturbation is applied.
+pub struct PerturbationWitness {
+    pub network: BooleanNetwork,
+    /// Graph of the perturbed witness network.
+    pub graph: SymbolicAsyncGraph,
+    pub attractors: Vec<GraphColoredVertices>,
+}
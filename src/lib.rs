@@ -2,13 +2,17 @@
 extern crate json;
 
 use crate::scc::ProgressTracker;
+use json::JsonValue;
 
 pub mod bdt;
 pub mod control;
 pub mod scc;
 /// Some utility methods that we can later move to std-lib
 pub mod util;
+/// Utilities for selecting witness parametrizations from colour sets.
+pub mod witness;
 
+mod _impl_computation_status;
 mod _impl_graph_task_context;
 
 /// A context object which aggregates all necessary information about a running task working with
@@ -20,3 +24,19 @@ pub struct GraphTaskContext {
     pub is_cancelled: cancel_this::CancelAtomic,
     progress: ProgressTracker,
 }
+
+/// Status of a long-running computation, reported in the same form for all computation
+/// types (attractor search, control, ...).
+pub struct ComputationStatus {
+    /// True if the computation thread is still alive.
+    pub running: bool,
+    pub cancelled: bool,
+    /// Milliseconds since the computation started (or its total duration once finished).
+    pub elapsed: u64,
+    /// Estimated fraction of the work that is done, between `0.0` and `1.0`.
+    pub progress: f64,
+    /// Number of results (attractors, perturbations, ...) found so far.
+    pub results_found: usize,
+    /// Progress details specific to the type of the computation.
+    pub details: JsonValue,
+}
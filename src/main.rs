@@ -8,28 +8,35 @@ use rocket::http::{ContentType, Header};
 use rocket::request::{FromRequest, Outcome, Request};
 use rocket::response::{self, Responder, Response};
 
-use biodivine_aeon_server::scc::{Behaviour, Class, Classifier};
-use biodivine_lib_param_bn::{BooleanNetwork, FnUpdate, ModelAnnotation};
+use biodivine_aeon_server::scc::{
+    AttractorGraph, Behaviour, Class, Classifier, attractors_to_dot, state_to_json,
+};
+use biodivine_lib_param_bn::{BooleanNetwork, FnUpdate, VariableId};
 use regex::Regex;
 use std::convert::{Infallible, TryFrom};
 
-use biodivine_aeon_server::GraphTaskContext;
 use biodivine_aeon_server::bdt::{AttributeId, Bdt, BdtNodeId};
-use biodivine_aeon_server::control::ControlComputation;
+use biodivine_aeon_server::control::{
+    self, ColorRestriction, ControlComputation, ControlRequest, PerturbationWitness,
+};
+use biodivine_aeon_server::scc::algo_attractor_search::attractor_search;
+use biodivine_aeon_server::scc::algo_basin_analysis::compute_basins;
+use biodivine_aeon_server::scc::algo_fixed_points::fixed_points;
+use biodivine_aeon_server::scc::algo_projection::Projection;
+use biodivine_aeon_server::scc::algo_reachability::{backward_reach, find_path, forward_reach};
 use biodivine_aeon_server::scc::algo_stability_analysis::{
-    StabilityVector, VariableStability, compute_stability,
+    JointStability, StabilityCache, StabilityProfiles, StabilityVector, VariableStability,
 };
+use biodivine_aeon_server::scc::algo_trap_spaces::{TrapSpaceKind, TrapSpaceSet};
 use biodivine_aeon_server::util::functional::Functional;
-use biodivine_algo_bdd_scc::attractor::{
-    AttractorConfig, InterleavedTransitionGuidedReduction, ItgrState, XieBeerelAttractors,
-};
+use biodivine_aeon_server::witness::{WitnessSampler, WitnessSelection, pick_minimal_witness};
+use biodivine_aeon_server::{ComputationStatus, GraphTaskContext};
 use biodivine_lib_param_bn::biodivine_std::bitvector::{ArrayBitVector, BitVector};
 use biodivine_lib_param_bn::biodivine_std::traits::Set;
-use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
-use biodivine_pbn_control::control::PhenotypeOscillationType;
-use biodivine_pbn_control::perturbation::PerturbationGraph;
+use biodivine_lib_param_bn::symbolic_async_graph::{
+    GraphColoredVertices, GraphColors, SymbolicAsyncGraph,
+};
 use cancel_this::{Cancellable, CancellationTrigger};
-use computation_process::{Computable, Generatable, Incomplete, Stateful};
 use json::JsonValue;
 use moka::sync::Cache;
 use num_bigint::BigUint;
@@ -40,7 +47,7 @@ use rocket::outcome::Outcome::Success;
 use rocket::{Config, Data, State};
 use rocket_cors::{AllowedOrigins, CorsOptions};
 use std::cmp::max;
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::HashMap;
 use std::error::Error;
 use std::net::IpAddr;
 use std::str::FromStr;
@@ -51,6 +58,20 @@ use tokio::io::AsyncReadExt;
 
 const MAX_MODEL_SIZE: ByteUnit = ByteUnit::Megabyte(10);
 
+/// The maximal number of witness networks that can be requested at once.
+const MAX_WITNESS_COUNT: usize = 100;
+/// Attractors with at least this many states are not exported explicitly, only as a summary.
+const MAX_EXPLICIT_ATTRACTOR_SIZE: usize = 500;
+/// The maximal number of trap spaces that can be enumerated at once.
+const MAX_TRAP_SPACE_COUNT: usize = 1000;
+/// The maximal number of projected valuations that can be enumerated at once.
+const MAX_PROJECTION_COUNT: usize = 10_000;
+/// Version of the structured JSON responses (attractors, results). Clients can request
+/// this version explicitly where the legacy format is still the default.
+const SCHEMA_VERSION: u32 = 2;
+/// Version of the original (hand-written) attractor JSON format.
+const LEGACY_SCHEMA_VERSION: u32 = 1;
+
 /// Identifies each tab/session that connects to the server. If a session key is not included,
 /// defaults to the empty key ("global session").
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
@@ -83,6 +104,14 @@ struct SessionState {
     bifurcation_tree: RwLock<Option<Bdt>>,
     /// Stores the current metadata, state, or result of the control computation.
     control_computation: RwLock<Option<ControlComputation>>,
+    /// Task context of the last reachability query (used for cancellation).
+    reachability_task: RwLock<Option<Arc<GraphTaskContext>>>,
+    /// Task context of the last basin computation (used for cancellation).
+    basin_task: RwLock<Option<Arc<GraphTaskContext>>>,
+    /// Task context of the last control perturbation drill-down (used for cancellation).
+    perturbation_task: RwLock<Option<Arc<GraphTaskContext>>>,
+    /// Cached stability analysis results for the current attractor computation.
+    stability_cache: StabilityCache,
 }
 
 impl SessionState {
@@ -124,6 +153,42 @@ impl SessionState {
             .expect("Correctness violation: lock tainted.")
     }
 
+    pub fn reachability_task_read(&self) -> RwLockReadGuard<'_, Option<Arc<GraphTaskContext>>> {
+        self.reachability_task
+            .read()
+            .expect("Correctness violation: lock tainted.")
+    }
+
+    pub fn reachability_task_write(&self) -> RwLockWriteGuard<'_, Option<Arc<GraphTaskContext>>> {
+        self.reachability_task
+            .write()
+            .expect("Correctness violation: lock tainted.")
+    }
+
+    pub fn basin_task_read(&self) -> RwLockReadGuard<'_, Option<Arc<GraphTaskContext>>> {
+        self.basin_task
+            .read()
+            .expect("Correctness violation: lock tainted.")
+    }
+
+    pub fn basin_task_write(&self) -> RwLockWriteGuard<'_, Option<Arc<GraphTaskContext>>> {
+        self.basin_task
+            .write()
+            .expect("Correctness violation: lock tainted.")
+    }
+
+    pub fn perturbation_task_read(&self) -> RwLockReadGuard<'_, Option<Arc<GraphTaskContext>>> {
+        self.perturbation_task
+            .read()
+            .expect("Correctness violation: lock tainted.")
+    }
+
+    pub fn perturbation_task_write(&self) -> RwLockWriteGuard<'_, Option<Arc<GraphTaskContext>>> {
+        self.perturbation_task
+            .write()
+            .expect("Correctness violation: lock tainted.")
+    }
+
     /// A helper function used to retrieve colors stored in a specific BDT node (if any).
     pub fn extract_tree_node_colors(&self, node_id: &str) -> Result<GraphColors, String> {
         let tree_guard = self.bifurcation_tree_read();
@@ -137,6 +202,42 @@ impl SessionState {
 
         Ok(tree.all_node_params(node))
     }
+
+    /// A helper function used to retrieve colors of a specific behaviour class (if any).
+    pub fn extract_class_colors(&self, class_str: &str) -> Result<GraphColors, String> {
+        let class = Class::try_read_string(class_str)?;
+        let cmp_guard = self.attractor_computation_read();
+        let Some(cmp) = cmp_guard.as_ref() else {
+            return Err("No results available.".to_string());
+        };
+        match try_get_class_params(&cmp.classifier, &class) {
+            None => Err("Classification still in progress.".to_string()),
+            Some(None) => Err("Specified class has no witness.".to_string()),
+            Some(Some(colors)) => Ok(colors),
+        }
+    }
+
+    /// Colours of the given restriction, transferred into the colour space of
+    /// the (non-perturbable) control graph.
+    pub fn extract_control_restriction(
+        &self,
+        control: &ControlComputation,
+        restriction: &ColorRestriction,
+    ) -> Result<GraphColors, String> {
+        let colors = match restriction {
+            ColorRestriction::TreeNode(node_id) => self.extract_tree_node_colors(node_id)?,
+            ColorRestriction::Class(class) => self.extract_class_colors(class)?,
+        };
+        let cmp_guard = self.attractor_computation_read();
+        let Some(cmp) = cmp_guard.as_ref() else {
+            return Err("No results available.".to_string());
+        };
+        control
+            .graph
+            .as_non_perturbable()
+            .transfer_colors_from(&colors, &cmp.graph)
+            .ok_or_else(|| "Restriction is not compatible with the control model.".to_string())
+    }
 }
 
 /// Just a type-alias so that we don't need to write the full type into each request function.
@@ -146,6 +247,7 @@ type SessionStorage = Cache<SessionKey, Arc<SessionState>>;
 struct AttractorComputation {
     timestamp: SystemTime,
     input_model: String,            // .aeon string representation of the model
+    network: BooleanNetwork,        // Parsed input model - used to create minimal witnesses
     task: GraphTaskContext, // A task context which keeps track of progress and cancellation.
     graph: SymbolicAsyncGraph, // Model graph - used to create witnesses
     classifier: Classifier, // Classifier used to store the results of the computation
@@ -168,6 +270,31 @@ impl AttractorComputation {
                 .as_millis()
         })
     }
+
+    /// Status of the attractor search, in the same form as other computation types.
+    pub fn status(&self) -> ComputationStatus {
+        let elapsed = self
+            .finished_timestamp
+            .unwrap_or_else(SystemTime::now)
+            .duration_since(self.timestamp)
+            .unwrap_or_default();
+        let progress = if self.thread.is_some() {
+            self.task.get_progress_fraction()
+        } else {
+            1.0
+        };
+        ComputationStatus {
+            running: self.thread.is_some(),
+            cancelled: self.task.is_cancelled(),
+            elapsed: u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+            progress,
+            results_found: self.task.get_result_count(),
+            details: object! {
+                "remaining" => self.task.get_progress_string(),
+                "numClasses" => self.classifier.try_get_num_classes(),
+            },
+        }
+    }
 }
 
 // Decision tree API design:
@@ -177,6 +304,8 @@ impl AttractorComputation {
 //    node objects.
 //    - /get_attributes/<node_id>: Obtain a list of attributes that can be applied to an unprocessed
 //    node. (This can take a while for large models) Returns an array of attribute objects.
+//    - /get_attribute_ranking: Obtain a global ranking of all attributes with respect to the
+//    root node (whole parameter space). Returns an array of attribute ranking objects.
 //    - /apply_attribute/<node_id>/<attribute_id>: Apply an attribute to an unprocessed node,
 //    replacing it with a decision and adding two new child nodes. Returns an array of tree nodes
 //    that have changed (i.e., the unprocessed node is now a decision node, and it has two children
@@ -188,10 +317,21 @@ impl AttractorComputation {
 // Models:
 //    - Tree node (three types):
 //      - Leaf node: { type: "leaf", id: usize, class: ClassString, cardinality: f64 }
-//      - Decision node: { type: "decision", id: usize, attribute_name: String, left: usize, right: usize }
+//      - Decision node: { type: "decision", id: usize, attribute_name: String,
+//        attribute_kind: AttributeKind, left: usize, right: usize }
 //      - Unprocessed node: { type: "unprocessed", id: usize, classes: ClassList }
-//    - Attribute { id: usize, name: String, gain: f64, left: ClassList, right: ClassList }
+//    - Attribute { id: usize, name: String, kind: AttributeKind, gain: f64, left: ClassList,
+//      right: ClassList }
+//    - Attribute kind: { type: String, ...payload } where type is one of `input`,
+//      `constant_parameter`, `essentiality`, `activation`, `inhibition`, `implicit_table_row`,
+//      `explicit_table_row` or `conditional_essentiality`. Variables and parameters in the
+//      payload (`variable`, `parameter`, `regulator`, `target`) are { id: usize, name: String },
+//      valuations/conditions are arrays of { id: usize, name: String, value: bool }.
 //    - Class list: array({ class: ClassString, cardinality: f64 })
+//    - Attribute ranking { id: usize, name: String, kind: AttributeKind, gain: f64,
+//      mutual_information: f64, positive_cardinality: String, negative_cardinality: String,
+//      classes: array({ class: ClassString, mutual_information: f64,
+//      positive_cardinality: String, negative_cardinality: String, separated: bool }) }
 //
 
 /// Obtain the graph structure of the decision tree as a list of nodes.
@@ -225,6 +365,20 @@ fn get_attributes(
     BackendResponse::ok_json(tree.attribute_gains_json(node))
 }
 
+/// Rank all tree attributes by how much they explain the class variation in the whole
+/// parameter space (root information gain, mutual information with each class, and which
+/// classes each attribute separates).
+#[get("/get_attribute_ranking")]
+fn get_attribute_ranking(key: SessionKey, storage: &State<SessionStorage>) -> BackendResponse {
+    let state = storage.get_with(key, Default::default);
+    let tree_guard = state.bifurcation_tree_read();
+    let Some(tree) = tree_guard.as_ref() else {
+        return BackendResponse::err("Decision tree not found.");
+    };
+
+    BackendResponse::ok_json(tree.attribute_ranking_json())
+}
+
 #[get("/get_stability_data/<node_id>/<behaviour_str>")]
 fn get_stability_data(
     key: SessionKey,
@@ -234,11 +388,7 @@ fn get_stability_data(
 ) -> BackendResult {
     let state = storage.get_with(key, Default::default);
 
-    let behavior = if behaviour_str == "total" {
-        None
-    } else {
-        Some(Behaviour::try_from(behaviour_str.as_str()).map_err(BackendResponse::err)?)
-    };
+    let behavior = parse_behaviour(&behaviour_str)?;
 
     // First, extract all colors in that tree node.
     let node_params = state
@@ -271,12 +421,18 @@ fn get_stability_data(
         return BackendResponse::err_result("No attractors with this property.");
     }
 
-    let stability_data = compute_stability(graph, &components);
     let mut response = JsonValue::new_array();
     for variable in graph.variables() {
+        let stability = state.stability_cache.get_or_compute(
+            graph,
+            &node_params,
+            behavior,
+            variable,
+            &components,
+        );
         response.push(object! {
             "variable": graph.get_variable_name(variable).clone(),
-            "data": stability_data[&variable].to_json(),
+            "data": stability.to_json(),
         })?;
     }
 
@@ -497,10 +653,13 @@ fn ping(key: SessionKey, storage: &State<SessionStorage>) -> BackendResponse {
         response["timestamp"] = (cmp.start_timestamp() as u64).into();
         response["is_cancelled"] = cmp.task.is_cancelled().into();
         response["progress"] = cmp.task.get_progress_string().into();
+        response["running"] = cmp.thread.is_some().into();
+        // Kept for clients that still read the old key.
         response["is_running"] = cmp.thread.is_some().into();
         if let Some(classes) = cmp.classifier.try_get_num_classes() {
             response["num_classes"] = classes.into();
         }
+        response["status"] = cmp.status().to_json();
     }
 
     BackendResponse::ok(response.to_string())
@@ -550,41 +709,38 @@ fn get_results(key: SessionKey, storage: &State<SessionStorage>) -> BackendRespo
         }
     };
 
-    let lines: Vec<String> = data
+    let data = data
         .iter()
-        .map(|(c, p)| {
-            format!(
-                "{{\"sat_count\":{},\"phenotype\":{}}}",
-                p.approx_cardinality(),
-                c
-            )
+        .map(|(class, params)| {
+            let phenotype = class
+                .get_vector()
+                .iter()
+                .map(|it| format!("{:?}", it))
+                .collect::<Vec<_>>();
+            object! {
+                "sat_count" => params.approx_cardinality(),
+                "phenotype" => phenotype,
+            }
         })
-        .collect();
-
-    println!("Result {:?}", lines);
-
-    let elapsed = elapsed.unwrap_or_default();
+        .collect::<Vec<_>>();
 
-    let mut json = String::new();
-    for line in lines.iter().take(lines.len() - 1) {
-        json += &format!("{},", line);
-    }
-    json = format!(
-        "{{ \"isPartial\":{}, \"data\":[{}{}], \"elapsed\":{} }}",
-        is_partial,
-        json,
-        lines.last().unwrap(),
-        elapsed,
-    );
+    let json = object! {
+        "schema_version" => SCHEMA_VERSION,
+        "isPartial" => is_partial,
+        "data" => data,
+        "elapsed" => u64::try_from(elapsed.unwrap_or_default()).unwrap_or(u64::MAX),
+    };
+    println!("Result {}", json["data"]);
 
-    BackendResponse::ok(&json)
+    BackendResponse::ok_json(json)
 }
 
-#[get("/get_tree_witness/<node_id>")]
+#[get("/get_tree_witness/<node_id>?<minimal>")]
 fn get_tree_witness(
     key: SessionKey,
     storage: &State<SessionStorage>,
     node_id: String,
+    minimal: Option<bool>,
 ) -> BackendResponse {
     let state = storage.get_with(key, Default::default);
     let tree_guard = state.bifurcation_tree_read();
@@ -597,13 +753,31 @@ fn get_tree_witness(
     };
 
     if let Some(params) = tree.params_for_leaf(node) {
-        get_witness_network(&state, params)
+        get_witness_network(&state, params, minimal.unwrap_or(false))
     } else {
         BackendResponse::err("Given node is not an unprocessed node.")
     }
 }
 
-#[get("/get_stability_witness/<node_id>/<behaviour_str>/<variable_str>/<vector_str>")]
+/// Unlike `get_tree_witness`, this works for any tree node, not just leaves.
+#[get("/get_tree_witnesses/<node_id>/<count>?<mode>&<seed>")]
+fn get_tree_witnesses(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    node_id: String,
+    count: String,
+    mode: Option<String>,
+    seed: Option<u64>,
+) -> BackendResult {
+    let state = storage.get_with(key, Default::default);
+    let (count, selection) = parse_witness_selection(&count, mode, seed)?;
+    let node_params = state
+        .extract_tree_node_colors(node_id.as_str())
+        .map_err(BackendResponse::err)?;
+    Ok(get_witness_networks(&state, &node_params, count, selection))
+}
+
+#[get("/get_stability_witness/<node_id>/<behaviour_str>/<variable_str>/<vector_str>?<minimal>")]
 fn get_stability_witness(
     key: SessionKey,
     storage: &State<SessionStorage>,
@@ -611,22 +785,79 @@ fn get_stability_witness(
     behaviour_str: String,
     variable_str: String,
     vector_str: String,
+    minimal: Option<bool>,
+) -> BackendResult {
+    let state = storage.get_with(key, Default::default);
+    let colors = extract_stability_vector_colors(
+        &state,
+        &node_id,
+        parse_behaviour(&behaviour_str)?,
+        &variable_str,
+        &vector_str,
+    )?;
+    Ok(get_witness_network(
+        &state,
+        &colors,
+        minimal.unwrap_or(false),
+    ))
+}
+
+#[get(
+    "/get_stability_witnesses/<node_id>/<behaviour_str>/<variable_str>/<vector_str>/<count>?<mode>&<seed>"
+)]
+#[allow(clippy::too_many_arguments)]
+fn get_stability_witnesses(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    node_id: String,
+    behaviour_str: String,
+    variable_str: String,
+    vector_str: String,
+    count: String,
+    mode: Option<String>,
+    seed: Option<u64>,
 ) -> BackendResult {
     let state = storage.get_with(key, Default::default);
+    let (count, selection) = parse_witness_selection(&count, mode, seed)?;
+    let colors = extract_stability_vector_colors(
+        &state,
+        &node_id,
+        parse_behaviour(&behaviour_str)?,
+        &variable_str,
+        &vector_str,
+    )?;
+    Ok(get_witness_networks(&state, &colors, count, selection))
+}
 
-    let behavior = if behaviour_str == "total" {
-        None
+/// **(internal)** Parse a behaviour given in a request path, where `total` stands for
+/// all behaviours (`None`).
+fn parse_behaviour(behaviour_str: &str) -> Result<Option<Behaviour>, BackendResponse> {
+    if behaviour_str == "total" {
+        Ok(None)
     } else {
-        Some(Behaviour::try_from(behaviour_str.as_str()).map_err(BackendResponse::err)?)
-    };
+        Behaviour::try_from(behaviour_str)
+            .map(Some)
+            .map_err(BackendResponse::err)
+    }
+}
 
-    let vector = StabilityVector::try_from(vector_str.as_str()).map_err(BackendResponse::err)?;
+/// A helper function which computes the colours of a tree node in which the given variable
+/// has the given stability vector (considering only attractors with the given behaviour).
+fn extract_stability_vector_colors(
+    state: &SessionState,
+    node_id: &str,
+    behavior: Option<Behaviour>,
+    variable_str: &str,
+    vector_str: &str,
+) -> Result<GraphColors, BackendResponse> {
+    let vector = StabilityVector::try_from(vector_str).map_err(BackendResponse::err)?;
 
     // First, extract all colors in that tree node.
     let node_params = state
-        .extract_tree_node_colors(node_id.as_str())
+        .extract_tree_node_colors(node_id)
         .map_err(BackendResponse::err)?;
 
+    // Then find all attractors of the graph
     let cmp_guard = state.attractor_computation_read();
     let Some(cmp) = cmp_guard.as_ref() else {
         return BackendResponse::err_result("No attractor data found.");
@@ -639,7 +870,7 @@ fn get_stability_witness(
     let variable = cmp
         .graph
         .symbolic_context()
-        .find_network_variable(variable_str.as_str());
+        .find_network_variable(variable_str);
     let Some(variable) = variable else {
         return BackendResponse::err_result(
             format!("Unknown graph variable `{}`.", variable_str).as_str(),
@@ -656,9 +887,15 @@ fn get_stability_witness(
         })
         .collect::<Vec<_>>();
 
-    let variable_stability = VariableStability::for_attractors(&cmp.graph, &components, variable);
+    let variable_stability = state.stability_cache.get_or_compute(
+        &cmp.graph,
+        &node_params,
+        behavior,
+        variable,
+        &components,
+    );
     if let Some(colors) = &variable_stability[vector] {
-        Ok(get_witness_network(&state, colors))
+        Ok(colors.clone())
     } else {
         BackendResponse::err_result(
             format!("No witness available for vector `{}`.", vector_str).as_str(),
@@ -666,124 +903,158 @@ fn get_stability_witness(
     }
 }
 
-#[get("/get_witness/<class_str>")]
-fn get_witness(
+#[get("/get_joint_stability_data/<node_id>/<behaviour_str>/<variables_str>")]
+fn get_joint_stability_data(
     key: SessionKey,
     storage: &State<SessionStorage>,
-    class_str: String,
+    node_id: String,
+    behaviour_str: String,
+    variables_str: String,
 ) -> BackendResult {
     let state = storage.get_with(key, Default::default);
-    let class = Class::try_read_string(class_str).map_err(BackendResponse::err)?;
-    let cmp_guard = state.attractor_computation_read();
-    let Some(cmp) = cmp_guard.as_ref() else {
-        return BackendResponse::err_result("No results available.");
-    };
-
-    let Some(has_class) = try_get_class_params(&cmp.classifier, &class) else {
-        return BackendResponse::err_result(
-            "Classification in progress. Cannot extract witness right now.",
-        );
-    };
-
-    if let Some(class) = has_class {
-        Ok(get_witness_network(&state, &class))
-    } else {
-        BackendResponse::err_result("Specified class has no witness.")
-    }
-}
-
-fn get_witness_network(state: &SessionState, colors: &GraphColors) -> BackendResponse {
+    let stability = compute_joint_stability(&state, &node_id, &behaviour_str, &variables_str)?;
     let cmp_guard = state.attractor_computation_read();
     let Some(cmp) = cmp_guard.as_ref() else {
-        return BackendResponse::err("No results available.");
+        return BackendResponse::err_result("No attractor data found.");
     };
-
-    let witness = cmp.graph.pick_witness(colors);
-    let layout = read_layout(cmp.input_model.as_str());
-    let mut model_string = format!("{}", witness); // convert back to AEON
-    model_string += "\n";
-    for (var, (x, y)) in layout {
-        model_string += format!("#position:{}:{},{}\n", var, x, y).as_str();
-    }
-    let (name, description) = read_metadata(cmp.input_model.as_str());
-    if let Some(name) = name {
-        model_string += format!("#name:{}\n", name).as_str();
-    }
-    if let Some(description) = description {
-        model_string += format!("#description:{}\n", description).as_str();
-    }
-    BackendResponse::ok_json(object! { "model" => model_string })
+    let variables = stability
+        .variables()
+        .iter()
+        .map(|var| cmp.graph.get_variable_name(*var).clone())
+        .collect::<Vec<_>>();
+    BackendResponse::ok_json_result(object! {
+        "variables" => variables,
+        "data" => stability.to_json(),
+    })
 }
 
-#[get("/get_tree_attractors/<node_id>")]
-fn get_tree_attractors(
+#[get(
+    "/get_joint_stability_witness/<node_id>/<behaviour_str>/<variables_str>/<vectors_str>?<minimal>"
+)]
+fn get_joint_stability_witness(
     key: SessionKey,
     storage: &State<SessionStorage>,
     node_id: String,
-) -> BackendResponse {
+    behaviour_str: String,
+    variables_str: String,
+    vectors_str: String,
+    minimal: Option<bool>,
+) -> BackendResult {
     let state = storage.get_with(key, Default::default);
-    let tree_guard = state.bifurcation_tree_read();
-    let Some(tree) = tree_guard.as_ref() else {
-        return BackendResponse::err("No tree present. Run computation first.");
-    };
-
-    let Some(node) = BdtNodeId::try_from_str(&node_id, tree) else {
-        return BackendResponse::err(format!("Invalid node id {node_id}."));
-    };
-
-    if let Some(params) = tree.params_for_leaf(node) {
-        get_witness_attractors(&state, params)
-    } else {
-        BackendResponse::err("Given node is not an unprocessed node.")
-    }
+    let colors = extract_joint_stability_colors(
+        &state,
+        &node_id,
+        &behaviour_str,
+        &variables_str,
+        &vectors_str,
+    )?;
+    Ok(get_witness_network(
+        &state,
+        &colors,
+        minimal.unwrap_or(false),
+    ))
 }
 
-#[get("/get_stability_attractors/<node_id>/<behaviour_str>/<variable_str>/<vector_str>")]
-fn get_stability_attractors(
+#[get(
+    "/get_joint_stability_attractors/<node_id>/<behaviour_str>/<variables_str>/<vectors_str>?<samples>&<quotient>&<schema>&<format>"
+)]
+#[allow(clippy::too_many_arguments)]
+fn get_joint_stability_attractors(
     key: SessionKey,
     storage: &State<SessionStorage>,
     node_id: String,
     behaviour_str: String,
-    variable_str: String,
-    vector_str: String,
-) -> BackendResult {
+    variables_str: String,
+    vectors_str: String,
+    samples: Option<usize>,
+    quotient: Option<String>,
+    schema: Option<u32>,
+    format: Option<String>,
+) -> ExportResult {
     let state = storage.get_with(key, Default::default);
+    let colors = extract_joint_stability_colors(
+        &state,
+        &node_id,
+        &behaviour_str,
+        &variables_str,
+        &vectors_str,
+    )?;
+    Ok(get_witness_attractors(
+        &state,
+        &colors,
+        &AttractorOptions::new(samples, quotient, schema, format)?,
+    ))
+}
 
-    let behavior = if behaviour_str == "total" {
-        None
-    } else {
-        Some(Behaviour::try_from(behaviour_str.as_str()).map_err(BackendResponse::err)?)
-    };
+/// Compute the joint stability of the given (comma-separated) variables in the attractors
+/// of a tree node, optionally restricted to attractors of the given behaviour (or `total`).
+fn compute_joint_stability(
+    state: &SessionState,
+    node_id: &str,
+    behaviour_str: &str,
+    variables_str: &str,
+) -> Result<JointStability, BackendResponse> {
+    with_node_components(
+        state,
+        node_id,
+        behaviour_str,
+        variables_str,
+        |graph, components, variables| JointStability::for_attractors(graph, components, variables),
+    )
+}
+
+/// Compute attractor-resolved stability profiles of the given (comma-separated) variables
+/// in the attractors of a tree node (see `compute_joint_stability`).
+fn compute_stability_profiles(
+    state: &SessionState,
+    node_id: &str,
+    behaviour_str: &str,
+    variables_str: &str,
+    ordered: bool,
+) -> Result<StabilityProfiles, BackendResponse> {
+    with_node_components(
+        state,
+        node_id,
+        behaviour_str,
+        variables_str,
+        |graph, components, variables| {
+            StabilityProfiles::for_attractors(graph, components, variables, ordered)
+        },
+    )
+}
 
-    let vector = StabilityVector::try_from(vector_str.as_str()).map_err(BackendResponse::err)?;
+/// **(internal)** Run an analysis of the attractors of a tree node (optionally restricted to
+/// the given behaviour, or `total`) and a (comma-separated) list of variables.
+fn with_node_components<R, F>(
+    state: &SessionState,
+    node_id: &str,
+    behaviour_str: &str,
+    variables_str: &str,
+    analysis: F,
+) -> Result<R, BackendResponse>
+where
+    F: FnOnce(&SymbolicAsyncGraph, &[GraphColoredVertices], &[VariableId]) -> R,
+{
+    let behavior = parse_behaviour(behaviour_str)?;
 
     // First, extract all colors in that tree node.
     let node_params = state
-        .extract_tree_node_colors(node_id.as_str())
+        .extract_tree_node_colors(node_id)
         .map_err(BackendResponse::err)?;
 
-    // Then find all attractors of the graph
     let cmp_guard = state.attractor_computation_read();
     let Some(cmp) = cmp_guard.as_ref() else {
         return BackendResponse::err_result("No attractor data found.");
     };
+    let variables = parse_variable_list(&cmp.graph, Some(variables_str.to_string()))?;
+    if variables.is_empty() {
+        return BackendResponse::err_result("No variables selected.");
+    }
 
+    // Only consider attractors that are relevant for the node colors.
     let components = cmp
         .classifier
-        .export_components_with_optional_class(behavior);
-
-    let variable = cmp
-        .graph
-        .symbolic_context()
-        .find_network_variable(variable_str.as_str());
-    let Some(variable) = variable else {
-        return BackendResponse::err_result(
-            format!("Unknown graph variable `{}`.", variable_str).as_str(),
-        );
-    };
-
-    // Now compute which attractors are actually relevant for the node colors
-    let components = components
+        .export_components_with_optional_class(behavior)
         .into_iter()
         .filter_map(|attractor| {
             attractor
@@ -792,27 +1063,169 @@ fn get_stability_attractors(
         })
         .collect::<Vec<_>>();
 
-    let variable_stability = VariableStability::for_attractors(&cmp.graph, &components, variable);
-    if let Some(colors) = &variable_stability[vector] {
-        Ok(get_witness_attractors(&state, colors))
+    if components.is_empty() {
+        return BackendResponse::err_result("No attractors with this property.");
+    }
+
+    Ok(analysis(&cmp.graph, &components, &variables))
+}
+
+/// Colours of a tree node where the given variables have the given (`;`-separated)
+/// stability vectors.
+fn extract_joint_stability_colors(
+    state: &SessionState,
+    node_id: &str,
+    behaviour_str: &str,
+    variables_str: &str,
+    vectors_str: &str,
+) -> Result<GraphColors, BackendResponse> {
+    let vectors = JointStability::parse_vectors(vectors_str).map_err(BackendResponse::err)?;
+    let stability = compute_joint_stability(state, node_id, behaviour_str, variables_str)?;
+    if vectors.len() != stability.variables().len() {
+        return BackendResponse::err_result(format!(
+            "Expected {} stability vectors, but found {}.",
+            stability.variables().len(),
+            vectors.len()
+        ));
+    }
+    if let Some(colors) = stability.get(&vectors) {
+        Ok(colors.clone())
     } else {
-        BackendResponse::err_result(
-            format!("No witness available for vector `{}`.", vector_str).as_str(),
-        )
+        BackendResponse::err_result(format!(
+            "No witness available for vectors `{}`.",
+            vectors_str
+        ))
     }
 }
 
-type EdgeList = Vec<(ArrayBitVector, ArrayBitVector)>;
+#[get("/get_stability_profiles/<node_id>/<behaviour_str>/<variables_str>?<ordered>")]
+fn get_stability_profiles(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    node_id: String,
+    behaviour_str: String,
+    variables_str: String,
+    ordered: Option<bool>,
+) -> BackendResult {
+    let state = storage.get_with(key, Default::default);
+    let profiles = compute_stability_profiles(
+        &state,
+        &node_id,
+        &behaviour_str,
+        &variables_str,
+        ordered.unwrap_or(false),
+    )?;
+    let cmp_guard = state.attractor_computation_read();
+    let Some(cmp) = cmp_guard.as_ref() else {
+        return BackendResponse::err_result("No attractor data found.");
+    };
+    let variables = profiles
+        .variables()
+        .iter()
+        .map(|var| cmp.graph.get_variable_name(*var).clone())
+        .collect::<Vec<_>>();
+    BackendResponse::ok_json_result(object! {
+        "variables" => variables,
+        "ordered" => profiles.is_ordered(),
+        "data" => profiles.to_json(),
+    })
+}
+
+#[get(
+    "/get_stability_profile_witness/<node_id>/<behaviour_str>/<variables_str>/<profiles_str>?<ordered>&<minimal>"
+)]
+#[allow(clippy::too_many_arguments)]
+fn get_stability_profile_witness(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    node_id: String,
+    behaviour_str: String,
+    variables_str: String,
+    profiles_str: String,
+    ordered: Option<bool>,
+    minimal: Option<bool>,
+) -> BackendResult {
+    let state = storage.get_with(key, Default::default);
+    let colors = extract_stability_profile_colors(
+        &state,
+        &node_id,
+        &behaviour_str,
+        &variables_str,
+        &profiles_str,
+        ordered.unwrap_or(false),
+    )?;
+    Ok(get_witness_network(
+        &state,
+        &colors,
+        minimal.unwrap_or(false),
+    ))
+}
+
+#[get(
+    "/get_stability_profile_attractors/<node_id>/<behaviour_str>/<variables_str>/<profiles_str>?<ordered>&<samples>&<quotient>&<schema>&<format>"
+)]
+#[allow(clippy::too_many_arguments)]
+fn get_stability_profile_attractors(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    node_id: String,
+    behaviour_str: String,
+    variables_str: String,
+    profiles_str: String,
+    ordered: Option<bool>,
+    samples: Option<usize>,
+    quotient: Option<String>,
+    schema: Option<u32>,
+    format: Option<String>,
+) -> ExportResult {
+    let state = storage.get_with(key, Default::default);
+    let colors = extract_stability_profile_colors(
+        &state,
+        &node_id,
+        &behaviour_str,
+        &variables_str,
+        &profiles_str,
+        ordered.unwrap_or(false),
+    )?;
+    Ok(get_witness_attractors(
+        &state,
+        &colors,
+        &AttractorOptions::new(samples, quotient, schema, format)?,
+    ))
+}
+
+/// Colours of a tree node where the attractors have the given stability profiles
+/// (see `StabilityProfiles::key_to_string`).
+fn extract_stability_profile_colors(
+    state: &SessionState,
+    node_id: &str,
+    behaviour_str: &str,
+    variables_str: &str,
+    profiles_str: &str,
+    ordered: bool,
+) -> Result<GraphColors, BackendResponse> {
+    let key = StabilityProfiles::parse_key(profiles_str).map_err(BackendResponse::err)?;
+    let profiles =
+        compute_stability_profiles(state, node_id, behaviour_str, variables_str, ordered)?;
+    if let Some(colors) = profiles.get(&key) {
+        Ok(colors.clone())
+    } else {
+        BackendResponse::err_result(format!(
+            "No witness available for profiles `{}`.",
+            profiles_str
+        ))
+    }
+}
 
-#[get("/get_attractors/<class_str>")]
-fn get_attractors(
+#[get("/get_witness/<class_str>?<minimal>")]
+fn get_witness(
     key: SessionKey,
     storage: &State<SessionStorage>,
     class_str: String,
+    minimal: Option<bool>,
 ) -> BackendResult {
     let state = storage.get_with(key, Default::default);
     let class = Class::try_read_string(class_str).map_err(BackendResponse::err)?;
-
     let cmp_guard = state.attractor_computation_read();
     let Some(cmp) = cmp_guard.as_ref() else {
         return BackendResponse::err_result("No results available.");
@@ -820,149 +1233,622 @@ fn get_attractors(
 
     let Some(has_class) = try_get_class_params(&cmp.classifier, &class) else {
         return BackendResponse::err_result(
-            "Classification still in progress. Cannot explore attractors now.",
+            "Classification in progress. Cannot extract witness right now.",
         );
     };
 
     if let Some(class) = has_class {
-        Ok(get_witness_attractors(state.as_ref(), &class))
+        Ok(get_witness_network(
+            &state,
+            &class,
+            minimal.unwrap_or(false),
+        ))
     } else {
         BackendResponse::err_result("Specified class has no witness.")
     }
 }
 
-fn get_witness_attractors(state: &SessionState, f_colors: &GraphColors) -> BackendResponse {
-    {
-        // Variables prefixed with f_ are from the original fully parametrized graph.
-        let cmp = state.attractor_computation_read();
-        if let Some(cmp) = &*cmp {
-            let f_classifier = &cmp.classifier;
-            let graph = &cmp.graph;
-            let f_witness_colour = f_colors.pick_singleton();
-            let witness_network: BooleanNetwork = graph.pick_witness(&f_witness_colour);
-            let witness_graph = SymbolicAsyncGraph::new(&witness_network).unwrap();
-            let witness_str = witness_network.to_string();
-            let f_witness_attractors = f_classifier.attractors(&f_witness_colour);
-            let variable_name_strings = witness_network
-                .variables()
-                .map(|id| format!("\"{}\"", witness_network.get_variable_name(id)));
-
-            let mut all_attractors: Vec<(Behaviour, EdgeList, HashSet<usize>)> = Vec::new();
-
-            // Note that the choice of graph/witness_graph is not arbitrary.
-            // The attractor set is from the original graph, but source_set/target_set
-            // are based on the witness_graph. This means they have different numbers
-            // of BDD variables inside!
-            let mut has_large_attractors = false;
-            for (f_attractor, behaviour) in f_witness_attractors.iter() {
-                println!(
-                    "Attractor {:?} state count: {}",
-                    behaviour,
-                    f_attractor.approx_cardinality()
-                );
-                let mut attractor_graph: Vec<(ArrayBitVector, ArrayBitVector)> = Vec::new();
-                let mut not_fixed_vars: HashSet<usize> = HashSet::new();
-                if *behaviour == Behaviour::Stability {
-                    // This is a sink - no edges
-                    assert_eq!(f_attractor.materialize().iter().count(), 1);
-                    let sink: ArrayBitVector = f_attractor.materialize().iter().next().unwrap();
-                    attractor_graph.push((sink.clone(), sink));
-                    for i in 0..witness_network.num_vars() {
-                        // In sink, we mark everything as "not-fixed" because we want to just display it normally.
-                        not_fixed_vars.insert(i);
-                    }
-                } else if f_attractor.approx_cardinality() >= 500.0 {
-                    has_large_attractors = true;
-                    // For large attractors, only show fixed values.
-                    let mut state_0 = ArrayBitVector::from(vec![false; graph.num_vars()]);
-                    let mut state_1 = ArrayBitVector::from(vec![true; graph.num_vars()]);
-                    for var in graph.variables() {
-                        let f_var_true = graph.fix_network_variable(var, true).vertices();
-                        let f_var_false = graph.fix_network_variable(var, false).vertices();
-                        let f_always_one = f_attractor.intersect(&f_var_false).is_empty();
-                        let f_always_zero = f_attractor.intersect(&f_var_true).is_empty();
-                        if f_always_one {
-                            state_0.set(var.into(), true);
-                        } else if f_always_zero {
-                            state_1.set(var.into(), false);
-                        } else {
-                            not_fixed_vars.insert(var.into());
-                        }
-                    }
-                    attractor_graph.push((state_0.clone(), state_1.clone()));
-                    attractor_graph.push((state_1, state_0));
-                } else {
-                    for source in f_attractor.materialize().iter() {
-                        let source_set = witness_graph.vertex(&source);
-                        let mut target_set = witness_graph.mk_empty_colored_vertices();
-                        for v in witness_graph.variables() {
-                            let post = witness_graph.var_post(v, &source_set);
-                            if !post.is_empty() {
-                                not_fixed_vars.insert(v.into());
-                                target_set = target_set.union(&post);
-                            }
-                        }
+#[get("/get_witnesses/<class_str>/<count>?<mode>&<seed>")]
+fn get_witnesses(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    class_str: String,
+    count: String,
+    mode: Option<String>,
+    seed: Option<u64>,
+) -> BackendResult {
+    let state = storage.get_with(key, Default::default);
+    let (count, selection) = parse_witness_selection(&count, mode, seed)?;
+    let class = Class::try_read_string(class_str).map_err(BackendResponse::err)?;
+    let class_params = {
+        let cmp_guard = state.attractor_computation_read();
+        let Some(cmp) = cmp_guard.as_ref() else {
+            return BackendResponse::err_result("No results available.");
+        };
 
-                        for target in target_set.vertices().materialize().iter() {
-                            attractor_graph.push((source.clone(), target));
-                        }
-                    }
-                }
+        let Some(has_class) = try_get_class_params(&cmp.classifier, &class) else {
+            return BackendResponse::err_result(
+                "Classification in progress. Cannot extract witness right now.",
+            );
+        };
+        has_class
+    };
 
-                all_attractors.push((*behaviour, attractor_graph, not_fixed_vars));
-            }
+    if let Some(class) = class_params {
+        Ok(get_witness_networks(&state, &class, count, selection))
+    } else {
+        BackendResponse::err_result("Specified class has no witness.")
+    }
+}
 
-            // now the data is stored in `all_attractors`, just convert it to JSON:
-            let mut json = String::new();
-
-            for (i, (behavior, graph, not_fixed)) in all_attractors.iter().enumerate() {
-                if i != 0 {
-                    json += ",";
-                } // json? no trailing commas for you
-                json += &format!("{{\"class\":\"{:?}\", \"graph\":[", behavior);
-                let mut edge_count = 0;
-                for (j, edge) in graph.iter().enumerate() {
-                    fn state_to_binary(
-                        state: &ArrayBitVector,
-                        not_fixed: &HashSet<usize>,
-                    ) -> String {
-                        let mut result = String::new();
-                        for i in 0..state.len() {
-                            if not_fixed.contains(&i) {
-                                result.push(if state.get(i) { '1' } else { '0' });
-                            } else {
-                                result.push(if state.get(i) { '⊤' } else { '⊥' });
-                            }
-                        }
-                        result
-                    }
-                    let from: String = state_to_binary(&edge.0, not_fixed);
-                    let to: String = state_to_binary(&edge.1, not_fixed);
-                    if j != 0 {
-                        json += ","
-                    }
-                    json += &format!("[\"{}\", \"{}\"]", from, to);
-                    edge_count += 1;
-                }
-                json += &format!("], \"edges\":{}}}", edge_count);
-            }
-            json = "{ \"attractors\":[".to_owned() + &json + "], \"variables\":[";
-            for (i, var) in variable_name_strings.enumerate() {
-                if i != 0 {
-                    json += ",";
-                }
-                json += var.as_str();
+/// Pick one witness network of the given colours and export it as an `.aeon` model.
+///
+/// If `minimal` is set, the witness with the simplest update functions is selected and
+/// its update functions are written in a minimized form (see `pick_minimal_witness`).
+fn get_witness_network(
+    state: &SessionState,
+    colors: &GraphColors,
+    minimal: bool,
+) -> BackendResponse {
+    let cmp_guard = state.attractor_computation_read();
+    let Some(cmp) = cmp_guard.as_ref() else {
+        return BackendResponse::err("No results available.");
+    };
+
+    let witness = if minimal {
+        match pick_minimal_witness(&cmp.graph, &cmp.network, colors) {
+            Ok(witness) => witness,
+            Err(error) => return BackendResponse::err(error),
+        }
+    } else {
+        cmp.graph.pick_witness(colors)
+    };
+    BackendResponse::ok_json(object! { "model" => witness_model_string(cmp, &witness) })
+}
+
+/// Enumerate up to `count` distinct witness networks of the given colour set, using
+/// the specified selection strategy. Each network is exported the same way as in
+/// `get_witness_network`.
+fn get_witness_networks(
+    state: &SessionState,
+    colors: &GraphColors,
+    count: usize,
+    selection: WitnessSelection,
+) -> BackendResponse {
+    let cmp_guard = state.attractor_computation_read();
+    let Some(cmp) = cmp_guard.as_ref() else {
+        return BackendResponse::err("No results available.");
+    };
+
+    let mut response = JsonValue::new_array();
+    for witness_colour in WitnessSampler::new(&cmp.graph, colors, selection).take(count) {
+        let witness = cmp.graph.pick_witness(&witness_colour);
+        response
+            .push(object! { "model" => witness_model_string(cmp, &witness) })
+            .unwrap();
+    }
+    BackendResponse::ok_json(response)
+}
+
+/// Convert a witness network to an `.aeon` string, including the layout and metadata
+/// of the input model of the given computation.
+fn witness_model_string(cmp: &AttractorComputation, witness: &BooleanNetwork) -> String {
+    let layout = read_layout(cmp.input_model.as_str());
+    let mut model_string = format!("{}", witness); // convert back to AEON
+    model_string += "\n";
+    for (var, (x, y)) in layout {
+        model_string += format!("#position:{}:{},{}\n", var, x, y).as_str();
+    }
+    let (name, description) = read_metadata(cmp.input_model.as_str());
+    if let Some(name) = name {
+        model_string += format!("#name:{}\n", name).as_str();
+    }
+    if let Some(description) = description {
+        model_string += format!("#description:{}\n", description).as_str();
+    }
+    model_string
+}
+
+/// Parse the witness count and selection strategy shared by all witness enumeration endpoints.
+fn parse_witness_selection(
+    count: &str,
+    mode: Option<String>,
+    seed: Option<u64>,
+) -> Result<(usize, WitnessSelection), BackendResponse> {
+    let count = count
+        .parse::<usize>()
+        .map_err(|_| BackendResponse::err(format!("Invalid witness count: {count}")))?;
+    if count == 0 || count > MAX_WITNESS_COUNT {
+        return Err(BackendResponse::err(format!(
+            "Witness count must be between 1 and {MAX_WITNESS_COUNT}."
+        )));
+    }
+    let mode = mode.unwrap_or_else(|| "first".to_string());
+    let selection =
+        WitnessSelection::try_from((mode.as_str(), seed)).map_err(BackendResponse::err)?;
+    Ok((count, selection))
+}
+
+#[get("/get_tree_attractors/<node_id>?<samples>&<quotient>&<schema>&<format>")]
+fn get_tree_attractors(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    node_id: String,
+    samples: Option<usize>,
+    quotient: Option<String>,
+    schema: Option<u32>,
+    format: Option<String>,
+) -> ExportResult {
+    let state = storage.get_with(key, Default::default);
+    let tree_guard = state.bifurcation_tree_read();
+    let Some(tree) = tree_guard.as_ref() else {
+        return Err(BackendResponse::err(
+            "No tree present. Run computation first.",
+        ));
+    };
+
+    let Some(node) = BdtNodeId::try_from_str(&node_id, tree) else {
+        return Err(BackendResponse::err(format!("Invalid node id {node_id}.")));
+    };
+
+    if let Some(params) = tree.params_for_leaf(node) {
+        let options = AttractorOptions::new(samples, quotient, schema, format)?;
+        Ok(get_witness_attractors(&state, params, &options))
+    } else {
+        Err(BackendResponse::err(
+            "Given node is not an unprocessed node.",
+        ))
+    }
+}
+
+#[get(
+    "/get_stability_attractors/<node_id>/<behaviour_str>/<variable_str>/<vector_str>?<samples>&<quotient>&<schema>&<format>"
+)]
+#[allow(clippy::too_many_arguments)]
+fn get_stability_attractors(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    node_id: String,
+    behaviour_str: String,
+    variable_str: String,
+    vector_str: String,
+    samples: Option<usize>,
+    quotient: Option<String>,
+    schema: Option<u32>,
+    format: Option<String>,
+) -> ExportResult {
+    let state = storage.get_with(key, Default::default);
+    let colors = extract_stability_vector_colors(
+        &state,
+        &node_id,
+        parse_behaviour(&behaviour_str)?,
+        &variable_str,
+        &vector_str,
+    )?;
+    Ok(get_witness_attractors(
+        &state,
+        &colors,
+        &AttractorOptions::new(samples, quotient, schema, format)?,
+    ))
+}
+
+#[get("/get_tree_basins/<node_id>?<samples>")]
+async fn get_tree_basins(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    node_id: String,
+    samples: Option<usize>,
+) -> BackendResult {
+    let state = storage.get_with(key, Default::default);
+    let colors = state
+        .extract_tree_node_colors(&node_id)
+        .map_err(BackendResponse::err)?;
+    run_witness_basins(state, colors, samples).await
+}
+
+#[get("/get_basins/<class_str>?<samples>")]
+async fn get_basins(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    class_str: String,
+    samples: Option<usize>,
+) -> BackendResult {
+    let state = storage.get_with(key, Default::default);
+    let colors = state
+        .extract_class_colors(&class_str)
+        .map_err(BackendResponse::err)?;
+    run_witness_basins(state, colors, samples).await
+}
+
+#[post("/cancel_basins")]
+fn cancel_basins(key: SessionKey, storage: &State<SessionStorage>) -> BackendResponse {
+    let state = storage.get_with(key, Default::default);
+    match state.basin_task_read().as_ref() {
+        None => err_response("No basin computation to cancel."),
+        Some(task) if task.is_cancelled() => err_response("Computation already cancelled."),
+        Some(task) => {
+            task.cancel();
+            BackendResponse::ok("\"ok\"")
+        }
+    }
+}
+
+/// **(internal)** Run `get_witness_basins` on a blocking thread. The computation replaces
+/// (and cancels) the previous basin computation of the session, and can be cancelled
+/// using `/cancel_basins`.
+async fn run_witness_basins(
+    state: Arc<SessionState>,
+    colors: GraphColors,
+    samples: Option<usize>,
+) -> BackendResult {
+    let task = Arc::new(GraphTaskContext::new());
+    if let Some(previous) = state.basin_task_write().replace(task.clone()) {
+        previous.cancel();
+    }
+    tokio::task::spawn_blocking(move || get_witness_basins(&state, &colors, samples, &task)).await?
+}
+
+/// Compute the weak and strong basins of all attractors of a witness parametrization
+/// picked from the given colours.
+fn get_witness_basins(
+    state: &SessionState,
+    f_colors: &GraphColors,
+    samples: Option<usize>,
+    task: &GraphTaskContext,
+) -> BackendResult {
+    let cmp_guard = state.attractor_computation_read();
+    let Some(cmp) = cmp_guard.as_ref() else {
+        return BackendResponse::err_result("No results available.");
+    };
+    if f_colors.is_empty() {
+        return BackendResponse::err_result("No colors available.");
+    }
+    let graph = &cmp.graph;
+    let samples = samples.unwrap_or(5).min(1000);
+    let f_witness_colour = f_colors.pick_singleton();
+    let f_witness_vertices = graph
+        .unit_colored_vertices()
+        .intersect_colors(&f_witness_colour);
+    let attractors = cmp
+        .classifier
+        .attractors(&f_witness_colour)
+        .into_iter()
+        .map(|(attractor, behaviour)| {
+            (f_witness_vertices.intersect_vertices(&attractor), behaviour)
+        })
+        .collect::<Vec<_>>();
+
+    let Some(basins) = compute_basins(graph, &attractors, task) else {
+        return BackendResponse::err_result("Basin computation cancelled.");
+    };
+    let witness_network = graph.pick_witness(&f_witness_colour);
+    BackendResponse::ok_json_result(object! {
+        "schema_version" => SCHEMA_VERSION,
+        "model" => witness_network.to_string(),
+        "state_count" => f_witness_vertices.vertices().exact_cardinality().to_string(),
+        "basins" => basins.iter().map(|it| it.to_json(graph, samples)).collect::<Vec<_>>(),
+    })
+}
+
+#[get("/get_projected_attractors/<class_str>?<variables>&<limit>")]
+fn get_projected_attractors(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    class_str: String,
+    variables: Option<String>,
+    limit: Option<usize>,
+) -> BackendResult {
+    let state = storage.get_with(key, Default::default);
+    let colors = state
+        .extract_class_colors(&class_str)
+        .map_err(BackendResponse::err)?;
+    get_projected_attractors_for(&state, &colors, None, variables, limit)
+}
+
+#[get("/get_tree_projected_attractors/<node_id>?<variables>&<limit>")]
+fn get_tree_projected_attractors(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    node_id: String,
+    variables: Option<String>,
+    limit: Option<usize>,
+) -> BackendResult {
+    let state = storage.get_with(key, Default::default);
+    let colors = state
+        .extract_tree_node_colors(&node_id)
+        .map_err(BackendResponse::err)?;
+    get_projected_attractors_for(&state, &colors, None, variables, limit)
+}
+
+#[get(
+    "/get_stability_projected_attractors/<node_id>/<behaviour_str>/<variable_str>/<vector_str>?<variables>&<limit>"
+)]
+#[allow(clippy::too_many_arguments)]
+fn get_stability_projected_attractors(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    node_id: String,
+    behaviour_str: String,
+    variable_str: String,
+    vector_str: String,
+    variables: Option<String>,
+    limit: Option<usize>,
+) -> BackendResult {
+    let state = storage.get_with(key, Default::default);
+    let behaviour = parse_behaviour(&behaviour_str)?;
+    let colors =
+        extract_stability_vector_colors(&state, &node_id, behaviour, &variable_str, &vector_str)?;
+    get_projected_attractors_for(&state, &colors, behaviour, variables, limit)
+}
+
+/// Enumerate attractor states of the given colours (and optionally behaviour), projected
+/// onto the comma-separated list of `variables`.
+fn get_projected_attractors_for(
+    state: &SessionState,
+    colors: &GraphColors,
+    behaviour: Option<Behaviour>,
+    variables: Option<String>,
+    limit: Option<usize>,
+) -> BackendResult {
+    let cmp_guard = state.attractor_computation_read();
+    let Some(cmp) = cmp_guard.as_ref() else {
+        return BackendResponse::err_result("No results available.");
+    };
+    let graph = &cmp.graph;
+    let variables = parse_variable_list(graph, variables)?;
+    let projection = cmp
+        .classifier
+        .project_attractors(graph, colors, behaviour, variables);
+    Ok(projection_response(graph, projection, limit))
+}
+
+#[get("/get_attractors/<class_str>?<samples>&<quotient>&<schema>&<format>")]
+fn get_attractors(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    class_str: String,
+    samples: Option<usize>,
+    quotient: Option<String>,
+    schema: Option<u32>,
+    format: Option<String>,
+) -> ExportResult {
+    let state = storage.get_with(key, Default::default);
+    let class = Class::try_read_string(class_str).map_err(BackendResponse::err)?;
+
+    let cmp_guard = state.attractor_computation_read();
+    let Some(cmp) = cmp_guard.as_ref() else {
+        return Err(BackendResponse::err("No results available."));
+    };
+
+    let Some(has_class) = try_get_class_params(&cmp.classifier, &class) else {
+        return Err(BackendResponse::err(
+            "Classification still in progress. Cannot explore attractors now.",
+        ));
+    };
+
+    if let Some(class) = has_class {
+        Ok(get_witness_attractors(
+            state.as_ref(),
+            &class,
+            &AttractorOptions::new(samples, quotient, schema, format)?,
+        ))
+    } else {
+        Err(BackendResponse::err("Specified class has no witness."))
+    }
+}
+
+/// Options that control how attractors are exported in `get_witness_attractors`.
+struct AttractorOptions {
+    /// Number of sampled states in each summary of a large attractor.
+    samples: usize,
+    /// Names of variables used to compute the attractor quotient (empty if no quotient).
+    quotient: Vec<String>,
+    /// Version of the response schema (see `SCHEMA_VERSION`).
+    schema: u32,
+    /// Export the attractors as a Graphviz transition system instead of JSON.
+    dot: bool,
+}
+
+impl AttractorOptions {
+    fn new(
+        samples: Option<usize>,
+        quotient: Option<String>,
+        schema: Option<u32>,
+        format: Option<String>,
+    ) -> Result<AttractorOptions, BackendResponse> {
+        let dot = match format.as_deref() {
+            None | Some("json") => false,
+            Some("dot") => true,
+            Some(format) => {
+                return Err(BackendResponse::err(format!(
+                    "Unknown export format `{format}`. Expected `json` or `dot`."
+                )));
             }
-            json += &format!(
-                "], \"model\":{}, \"has_large_attractors\": {}",
-                &object! { "model" => witness_str }.to_string(),
-                has_large_attractors
+        };
+        Ok(AttractorOptions {
+            samples: samples.unwrap_or(20).min(1000),
+            quotient: quotient
+                .map(|it| {
+                    it.split(',')
+                        .map(|name| name.trim().to_string())
+                        .filter(|name| !name.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            schema: schema.unwrap_or(LEGACY_SCHEMA_VERSION),
+            dot,
+        })
+    }
+}
+
+fn get_witness_attractors(
+    state: &SessionState,
+    f_colors: &GraphColors,
+    options: &AttractorOptions,
+) -> ExportResponse {
+    // Variables prefixed with f_ are from the original fully parametrized graph.
+    let cmp = state.attractor_computation_read();
+    let Some(cmp) = &*cmp else {
+        return BackendResponse::err("No results available.").into();
+    };
+    if options.schema != LEGACY_SCHEMA_VERSION && options.schema != SCHEMA_VERSION {
+        return BackendResponse::err(format!(
+            "Unsupported schema version {}. Expected {} or {}.",
+            options.schema, LEGACY_SCHEMA_VERSION, SCHEMA_VERSION
+        ))
+        .into();
+    }
+    let graph = &cmp.graph;
+    let mut quotient_variables = Vec::new();
+    for name in &options.quotient {
+        let Some(var) = graph.symbolic_context().find_network_variable(name) else {
+            return BackendResponse::err(format!("Unknown graph variable `{}`.", name)).into();
+        };
+        quotient_variables.push(var);
+    }
+
+    let f_witness_colour = f_colors.pick_singleton();
+    let witness_network: BooleanNetwork = graph.pick_witness(&f_witness_colour);
+    let f_witness_vertices = graph
+        .unit_colored_vertices()
+        .intersect_colors(&f_witness_colour);
+    let attractors = cmp
+        .classifier
+        .attractors(&f_witness_colour)
+        .into_iter()
+        .map(|(f_attractor, behaviour)| {
+            println!(
+                "Attractor {:?} state count: {}",
+                behaviour,
+                f_attractor.approx_cardinality()
             );
-            BackendResponse::ok(&(json + "}"))
-        } else {
-            BackendResponse::err("No results available.")
+            AttractorGraph::compute(
+                graph,
+                &f_witness_vertices.intersect_vertices(&f_attractor),
+                behaviour,
+                MAX_EXPLICIT_ATTRACTOR_SIZE,
+                options.samples,
+                &quotient_variables,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>();
+    let attractors = match attractors {
+        Ok(attractors) => attractors,
+        Err(error) => return BackendResponse::err(error).into(),
+    };
+
+    let variables = graph
+        .variables()
+        .map(|var| graph.get_variable_name(var).clone())
+        .collect::<Vec<_>>();
+    if options.dot {
+        return ExportResponse::File(FileResponse {
+            file_name: "attractors.dot".to_string(),
+            content_type: ContentType::new("text", "vnd.graphviz"),
+            content: attractors_to_dot(graph, &attractors),
+        });
+    }
+    let json = if options.schema == LEGACY_SCHEMA_VERSION {
+        object! {
+            "attractors" => attractors.iter().map(|it| legacy_attractor_json(graph, it)).collect::<Vec<_>>(),
+            "variables" => variables,
+            "model" => witness_network.to_string(),
+            "has_large_attractors" => attractors.iter().any(|it| it.is_large()),
+        }
+    } else {
+        object! {
+            "schema_version" => SCHEMA_VERSION,
+            "attractors" => attractors.iter().map(|it| it.to_json(graph)).collect::<Vec<_>>(),
+            "variables" => variables,
+            "model" => witness_network.to_string(),
         }
+    };
+    BackendResponse::ok_json(json).into()
+}
+
+/// **(internal)** Convert an attractor to the legacy (schema version 1) JSON format.
+///
+/// In this format, the attractor is a list of edges between states written as strings, where
+/// fixed variables use `⊤`/`⊥` and free variables use `1`/`0`. Sinks are written as one
+/// self-loop, and large attractors as two "fake" edges between the extreme states of the
+/// attractor subspace. The variable updated by each edge is given in `graph_variables`
+/// (`null` for the artificial edges of sinks and large attractors).
+fn legacy_attractor_json(graph: &SymbolicAsyncGraph, attractor: &AttractorGraph) -> JsonValue {
+    fn state_to_binary(state: &[bool], is_free: &[bool]) -> String {
+        state
+            .iter()
+            .zip(is_free)
+            .map(|(value, is_free)| match (*value, *is_free) {
+                (true, true) => '1',
+                (false, true) => '0',
+                (true, false) => '⊤',
+                (false, false) => '⊥',
+            })
+            .collect()
     }
+    fn state_values(state: &ArrayBitVector) -> Vec<bool> {
+        (0..state.len()).map(|i| state.get(i)).collect()
+    }
+
+    let num_vars = attractor.subspace.len();
+    let mut edges: Vec<(Vec<bool>, Vec<bool>, Option<VariableId>)> = Vec::new();
+    let mut is_free = vec![false; num_vars];
+    if attractor.behaviour == Behaviour::Stability && !attractor.is_large() {
+        // In sink, we mark everything as "not-fixed" because we want to just display it normally.
+        is_free = vec![true; num_vars];
+        let sink = state_values(&attractor.states[0]);
+        edges.push((sink.clone(), sink, None));
+    } else if attractor.is_large() {
+        // For large attractors, only show fixed values.
+        let state_0 = attractor
+            .subspace
+            .iter()
+            .map(|it| it.unwrap_or(false))
+            .collect::<Vec<_>>();
+        let state_1 = attractor
+            .subspace
+            .iter()
+            .map(|it| it.unwrap_or(true))
+            .collect::<Vec<_>>();
+        for var in attractor.free_variables() {
+            is_free[var.to_index()] = true;
+        }
+        edges.push((state_0.clone(), state_1.clone(), None));
+        edges.push((state_1, state_0, None));
+    } else {
+        for edge in &attractor.edges {
+            is_free[edge.variable.to_index()] = true;
+            edges.push((
+                state_values(&attractor.states[edge.source]),
+                state_values(&attractor.states[edge.target]),
+                Some(edge.variable),
+            ));
+        }
+    }
+
+    let edge_variables = edges
+        .iter()
+        .map(|(_, _, var)| var.map(|var| graph.get_variable_name(var).clone()))
+        .collect::<Vec<_>>();
+    let edges = edges
+        .iter()
+        .map(|(from, to, _)| {
+            array![
+                state_to_binary(from, &is_free),
+                state_to_binary(to, &is_free)
+            ]
+        })
+        .collect::<Vec<_>>();
+    let mut result = object! {
+        "class" => format!("{:?}", attractor.behaviour),
+        "edges" => edges.len(),
+        "graph" => edges,
+        "graph_variables" => edge_variables,
+    };
+    if let Some(summary) = &attractor.summary {
+        result.insert("summary", summary.to_json(graph)).unwrap();
+    }
+    result
 }
 
 #[get("/get_control_computation_status")]
@@ -986,84 +1872,664 @@ async fn get_control_computation_status(
         "computationStarted" => u64::try_from(unix_timestamp).unwrap_or(u64::MAX),
         "computationCancelled" => cmp.is_cancelled.is_cancelled(),
         "isRunning" => cmp.thread.is_some(),
+        "mode" => cmp.mode.to_string(),
+        "phenotype" => cmp.phenotype_expression.clone(),
+        "quantifier" => cmp.quantifier.to_string(),
+        "status" => cmp.status().to_json(),
         "elapsed" => cmp.elapsed_millis(),
         "version" => VERSION.to_string(),
     };
     BackendResponse::ok(stats.to_string())
 }
 
-#[get("/get_control_stats")]
-async fn get_control_stats(key: SessionKey, storage: &State<SessionStorage>) -> BackendResponse {
+/// Statistics of the current control results. Robustness is also reported within
+/// the colours of the given tree node or class (`node_id`/`class`), or within the restriction
+/// of the computation if neither is given.
+#[get("/get_control_stats?<node_id>&<class>")]
+async fn get_control_stats(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    node_id: Option<String>,
+    class: Option<String>,
+) -> BackendResult {
     let state = storage.get_with(key, Default::default);
     let cmp_guard = state.control_computation_read();
     let Some(cmp) = cmp_guard.as_ref() else {
-        return BackendResponse::err("No computation found.");
+        return BackendResponse::err_result("No computation found.");
     };
+    let restriction = control_restriction(&state, cmp, node_id, class)?;
 
     let unit = cmp.graph.as_non_perturbable().mk_unit_colors();
-    if let Some(results) = cmp.results.as_ref() {
-        let mut minimal_perturbation: Option<usize> = None;
-        let mut max_robustness: Option<f64> = None;
-        for (k, value) in results {
-            if k.len() < minimal_perturbation.unwrap_or(usize::MAX) {
-                minimal_perturbation = Some(k.len());
-            }
-
-            let p_card = value.exact_cardinality() * 1_000_000u32;
-            let u_card = unit.exact_cardinality();
+    let results = cmp.results();
+    let mut minimal_perturbation: Option<usize> = None;
+    let mut max_robustness: Option<f64> = None;
+    let mut max_restricted_robustness: Option<f64> = None;
+    for (k, value) in results.iter() {
+        if k.len() < minimal_perturbation.unwrap_or(usize::MAX) {
+            minimal_perturbation = Some(k.len());
+        }
 
-            let robustness: BigUint = p_card / u_card;
-            let robustness: f64 = robustness.to_f64().unwrap_or(f64::NAN) / 1_000_000.0;
+        let robustness = cmp.robustness(value);
+        if robustness > max_robustness.unwrap_or(0.0) {
+            max_robustness = Some(robustness);
+        }
 
-            if robustness > max_robustness.unwrap_or(0.0) {
-                max_robustness = Some(robustness);
+        if let Some(restriction) = restriction.as_ref() {
+            let robustness = cmp.restricted_robustness(value, restriction);
+            if robustness > max_restricted_robustness.unwrap_or(0.0) {
+                max_restricted_robustness = Some(robustness);
             }
         }
-        let stats = object! {
-            "allColorsCount": unit.exact_cardinality().to_usize().unwrap_or(usize::MAX),
-            "perturbationCount": results.len(),
-            "minimalPerturbationSize": minimal_perturbation,
-            "maximalPerturbationRobustness": max_robustness,
-            "elapsed": cmp.elapsed_millis(),
-        };
-        BackendResponse::ok(stats.to_string())
-    } else {
-        BackendResponse::err("No results found.")
     }
+    let pareto_front = cmp
+        .pareto_front(&results)
+        .into_iter()
+        .map(|i| {
+            let (perturbation, colors) = &results[i];
+            object! {
+                "index": i,
+                "perturbation": perturbation.clone(),
+                "cost": cmp.cost(perturbation),
+                "robustness": cmp.robustness(colors),
+            }
+        })
+        .collect::<Vec<_>>();
+    let stats = object! {
+        "allColorsCount": unit.exact_cardinality().to_usize().unwrap_or(usize::MAX),
+        "perturbationCount": results.len(),
+        "minimalPerturbationSize": minimal_perturbation,
+        "maximalPerturbationRobustness": max_robustness,
+        "elapsed": cmp.elapsed_millis(),
+        "isPartial": cmp.thread.is_some(),
+        "mode": cmp.mode.to_string(),
+        "phenotype": cmp.phenotype_expression.clone(),
+        "quantifier": cmp.quantifier.to_string(),
+        "paretoFront": pareto_front,
+        "restrictedColorsCount": restriction
+            .as_ref()
+            .map(|it| it.exact_cardinality().to_usize().unwrap_or(usize::MAX)),
+        "maximalRestrictedRobustness": max_restricted_robustness,
+    };
+    BackendResponse::ok_result(stats.to_string())
 }
 
-#[get("/get_control_results")]
-async fn get_control_results(key: SessionKey, storage: &State<SessionStorage>) -> BackendResponse {
+/// Return the perturbations discovered so far (the result is partial while the computation
+/// is running).
+///
+/// By default, the legacy format (a plain array of results) is used. With `schema` set to
+/// `SCHEMA_VERSION`, the results are wrapped in an object which also reports whether
+/// the results are partial and `progress` as the fraction of the requested `result_count`
+/// that has already been found.
+///
+/// As in `get_control_stats`, robustness is also reported within the given tree node or class,
+/// or the restriction of the computation.
+#[get("/get_control_results?<node_id>&<class>&<schema>")]
+async fn get_control_results(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    node_id: Option<String>,
+    class: Option<String>,
+    schema: Option<u32>,
+) -> BackendResult {
     let state = storage.get_with(key, Default::default);
     let cmp_guard = state.control_computation_read();
     let Some(cmp) = cmp_guard.as_ref() else {
-        return BackendResponse::err("No computation found.");
+        return BackendResponse::err_result("No computation found.");
     };
+    let schema = schema.unwrap_or(LEGACY_SCHEMA_VERSION);
+    if schema != LEGACY_SCHEMA_VERSION && schema != SCHEMA_VERSION {
+        return BackendResponse::err_result(format!(
+            "Unsupported schema version {}. Expected {} or {}.",
+            schema, LEGACY_SCHEMA_VERSION, SCHEMA_VERSION
+        ));
+    }
+    let restriction = control_restriction(&state, cmp, node_id, class)?;
 
-    let unit = cmp.graph.as_non_perturbable().mk_unit_colors();
-    if let Some(results) = cmp.results.as_ref() {
-        let mut response = JsonValue::new_array();
-        for (key, value) in results {
-            // The following method always gives an approximation up to 6 decimal places, even if the
-            // cardinality overflows to f64::infinity.
-
-            let p_card = value.exact_cardinality() * 1_000_000u32;
-            let u_card = unit.exact_cardinality();
-
-            let robustness: BigUint = p_card / u_card;
-            let robustness: f64 = robustness.to_f64().unwrap_or(f64::NAN) / 1_000_000.0;
-            response
-                .push(object! {
-                    "perturbation": key.clone(),
-                    "color_count": value.exact_cardinality().to_u64().unwrap_or(u64::MAX),
-                    "robustness": robustness,
+    let mut response = JsonValue::new_array();
+    for (key, value) in cmp.results().iter() {
+        response
+            .push(object! {
+                "perturbation": key.clone(),
+                "color_count": value.exact_cardinality().to_u64().unwrap_or(u64::MAX),
+                "robustness": cmp.robustness(value),
+                "restricted_robustness": restriction
+                    .as_ref()
+                    .map(|it| cmp.restricted_robustness(value, it)),
+                "cost": cmp.cost(key),
+            })
+            .unwrap();
+    }
+    if schema == LEGACY_SCHEMA_VERSION {
+        return BackendResponse::ok_result(response.to_string());
+    }
+    let response = object! {
+        "schema_version": SCHEMA_VERSION,
+        "results": response,
+        "isPartial": cmp.thread.is_some(),
+        "progress": cmp.progress(),
+        "resultCount": cmp.result_count,
+        "minRobustness": cmp.min_robustness,
+    };
+    BackendResponse::ok_result(response.to_string())
+}
+
+/// Resolve the colour restriction of control result endpoints: the tree node or class given
+/// in the query, or the restriction of the computation itself.
+fn control_restriction(
+    state: &SessionState,
+    cmp: &ControlComputation,
+    node_id: Option<String>,
+    class: Option<String>,
+) -> Result<Option<GraphColors>, BackendResponse> {
+    let restriction = match (node_id, class) {
+        (Some(_), Some(_)) => {
+            return Err(BackendResponse::err(
+                "Specify either `node_id` or `class`, not both.",
+            ));
+        }
+        (Some(node_id), None) => ColorRestriction::TreeNode(node_id),
+        (None, Some(class)) => ColorRestriction::Class(class),
+        (None, None) => return Ok(cmp.restriction.clone()),
+    };
+    state
+        .extract_control_restriction(cmp, &restriction)
+        .map(Some)
+        .map_err(BackendResponse::err)
+}
+
+/// Export the current control results, either as a downloadable CSV file (`format=csv`,
+/// default) with one row per perturbation, or as a JSON document (`format=json`) which can be
+/// later compared with other runs using `/compare_control_results`.
+#[get("/export_control_results?<format>")]
+async fn export_control_results(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    format: Option<String>,
+) -> Result<ExportResponse, BackendResponse> {
+    let state = storage.get_with(key, Default::default);
+    let cmp_guard = state.control_computation_read();
+    let Some(cmp) = cmp_guard.as_ref() else {
+        return Err(BackendResponse::err("No computation found."));
+    };
+    match format.as_deref() {
+        None | Some("csv") => Ok(ExportResponse::File(FileResponse {
+            file_name: format!("control-results-{}.csv", cmp.start_timestamp()),
+            content_type: ContentType::CSV,
+            content: cmp.export_csv(),
+        })),
+        Some("json") => Ok(ExportResponse::Json(BackendResponse::ok_json(
+            cmp.export_json(),
+        ))),
+        Some(format) => Err(BackendResponse::err(format!(
+            "Unknown export format `{format}`. Expected `csv` or `json`."
+        ))),
+    }
+}
+
+/// Compare two control result exports (see `/export_control_results?format=json`). The body
+/// is an object `{ "left": <export>, "right": <export> }`. Reports perturbations gained and
+/// lost in `right` compared to `left`, and the robustness change of the common ones.
+#[post("/compare_control_results", format = "plain", data = "<data>")]
+async fn compare_control_results(data: Data<'_>) -> BackendResult {
+    let query = load_string(data, MAX_MODEL_SIZE).await?;
+    let query = json::parse(&query)?;
+    let diff = control::compare_control_results(&query["left"], &query["right"])
+        .map_err(BackendResponse::err)?;
+    BackendResponse::ok_json_result(diff)
+}
+
+/// Details of the control result with the given `index` (see `get_control_results`):
+///
+///  - The number of working colours in each bifurcation class and decision tree leaf of the
+///    current attractor computation (if it was computed for a compatible model).
+///  - A witness network for one working and one failing parametrization, together with
+///    the attractors reached after the perturbation is applied (not available for
+///    `temporary` control).
+///
+/// The attractor search replaces (and cancels) the previous drill-down of the session, and
+/// can be cancelled using `/cancel_control_perturbation`.
+#[get("/get_control_perturbation/<index>?<samples>")]
+async fn get_control_perturbation(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    index: usize,
+    samples: Option<usize>,
+) -> BackendResult {
+    let state = storage.get_with(key, Default::default);
+    let samples = samples.unwrap_or(20).min(1000);
+    let task = Arc::new(GraphTaskContext::new());
+    if let Some(previous) = state.perturbation_task_write().replace(task.clone()) {
+        previous.cancel();
+    }
+    tokio::task::spawn_blocking(move || {
+        // Only collect the witness networks while holding the computation, so that the
+        // attractor search does not block the control thread.
+        let (perturbation, phenotype, witnesses, summary) = {
+            let cmp_guard = state.control_computation_read();
+            let Some(cmp) = cmp_guard.as_ref() else {
+                return BackendResponse::err_result("No computation found.");
+            };
+            let Some((perturbation, colors)) = cmp.results().get(index).cloned() else {
+                return BackendResponse::err_result(format!(
+                    "No control result with index {index}."
+                ));
+            };
+            let working = cmp.witness_network(&colors).map_err(BackendResponse::err)?;
+            let failing = cmp
+                .witness_network(&cmp.failing_colors(&colors))
+                .map_err(BackendResponse::err)?;
+            let (classes, tree_leaves) = control_colors_breakdown(&state, cmp, &colors);
+            let summary = object! {
+                "perturbation" => perturbation.clone(),
+                "color_count" => colors.exact_cardinality().to_u64().unwrap_or(u64::MAX),
+                "robustness" => cmp.robustness(&colors),
+                "classes" => classes,
+                "tree_leaves" => tree_leaves,
+            };
+            (
+                perturbation,
+                cmp.phenotype.clone(),
+                [working, failing],
+                summary,
+            )
+        };
+
+        let witness_json = |network: Option<BooleanNetwork>| -> Result<JsonValue, BackendResponse> {
+            let Some(network) = network else {
+                return Ok(JsonValue::Null);
+            };
+            let witness = PerturbationWitness::new(network, &perturbation, &task)
+                .map_err(BackendResponse::err)?;
+            Ok(witness.to_json(&phenotype, samples))
+        };
+        let [working, failing] = witnesses;
+        let mut response = summary;
+        response["working_witness"] = witness_json(working)?;
+        response["failing_witness"] = witness_json(failing)?;
+        BackendResponse::ok_json_result(response)
+    })
+    .await?
+}
+
+#[post("/cancel_control_perturbation")]
+fn cancel_control_perturbation(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+) -> BackendResponse {
+    let state = storage.get_with(key, Default::default);
+    match state.perturbation_task_read().as_ref() {
+        None => err_response("No perturbation drill-down to cancel."),
+        Some(task) if task.is_cancelled() => err_response("Computation already cancelled."),
+        Some(task) => {
+            task.cancel();
+            BackendResponse::ok("\"ok\"")
+        }
+    }
+}
+
+/// Split the given control colours by the bifurcation classes and decision tree leaves
+/// of the current attractor computation. Both are `null` if there is no finished attractor
+/// computation, or its model is not compatible with the control model.
+fn control_colors_breakdown(
+    state: &SessionState,
+    control: &ControlComputation,
+    colors: &GraphColors,
+) -> (JsonValue, JsonValue) {
+    let cmp_guard = state.attractor_computation_read();
+    let Some(cmp) = cmp_guard.as_ref() else {
+        return (JsonValue::Null, JsonValue::Null);
+    };
+    let Some(colors) = cmp
+        .graph
+        .transfer_colors_from(colors, control.graph.as_non_perturbable())
+    else {
+        return (JsonValue::Null, JsonValue::Null);
+    };
+    let Some(classes) = cmp.classifier.try_export_result() else {
+        return (JsonValue::Null, JsonValue::Null);
+    };
+
+    let mut classes = classes.into_iter().collect::<Vec<_>>();
+    classes.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let classes = classes
+        .into_iter()
+        .filter_map(|(class, params)| {
+            let params = params.intersect(&colors);
+            if params.is_empty() {
+                return None;
+            }
+            let phenotype = class
+                .get_vector()
+                .iter()
+                .map(|it| format!("{:?}", it))
+                .collect::<Vec<_>>();
+            Some(object! {
+                "phenotype" => phenotype,
+                "color_count" => params.approx_cardinality(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let tree_guard = state.bifurcation_tree_read();
+    let tree_leaves = if let Some(tree) = tree_guard.as_ref() {
+        let leaves = tree
+            .nodes()
+            .filter_map(|node| {
+                let params = tree.params_for_leaf(node)?.intersect(&colors);
+                if params.is_empty() {
+                    return None;
+                }
+                Some(object! {
+                    "node_id" => node.to_index(),
+                    "color_count" => params.approx_cardinality(),
                 })
-                .unwrap();
+            })
+            .collect::<Vec<_>>();
+        JsonValue::from(leaves)
+    } else {
+        JsonValue::Null
+    };
+
+    (JsonValue::from(classes), tree_leaves)
+}
+
+/// Check if the given subspace `target` is reachable from subspace `source`, and for which
+/// parametrizations. The query is a JSON object of the following form:
+///
+/// ```json
+/// { "source": { "A": true }, "target": { "B": false, "C": true }, "node_id": 3 }
+/// ```
+///
+/// Optionally, the colours can be restricted to a tree node (`node_id`, a string or a number)
+/// or a behaviour class (`class`). Both forward reachability (from `source`) and backward
+/// reachability (to `target`) are computed: the result contains the colours where the
+/// target is reachable, the number of target states reachable from the source and of source
+/// states that can reach the target, as well as a witness network and an example path for
+/// one such colour. The computation can be cancelled using `/cancel_reachability`.
+#[post("/reachability", format = "plain", data = "<data>")]
+async fn reachability(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    data: Data<'_>,
+) -> BackendResult {
+    let state = storage.get_with(key, Default::default);
+    let query = load_string(data, ByteUnit::Megabyte(1)).await?;
+    let query = json::parse(&query)?;
+    let task = Arc::new(GraphTaskContext::new());
+    if let Some(previous) = state.reachability_task_write().replace(task.clone()) {
+        previous.cancel();
+    }
+    tokio::task::spawn_blocking(move || run_reachability_query(&state, &query, &task)).await?
+}
+
+#[post("/cancel_reachability")]
+fn cancel_reachability(key: SessionKey, storage: &State<SessionStorage>) -> BackendResponse {
+    let state = storage.get_with(key, Default::default);
+    match state.reachability_task_read().as_ref() {
+        None => err_response("No reachability query to cancel."),
+        Some(task) if task.is_cancelled() => err_response("Query already cancelled."),
+        Some(task) => {
+            task.cancel();
+            BackendResponse::ok("\"ok\"")
         }
-        BackendResponse::ok(response.to_string())
+    }
+}
+
+/// **(internal)** Evaluate a reachability query (see `reachability`).
+fn run_reachability_query(
+    state: &SessionState,
+    query: &JsonValue,
+    task: &GraphTaskContext,
+) -> BackendResult {
+    // Resolve the colour restriction first, since it needs to lock the session state.
+    let node_id = &query["node_id"];
+    let node_id = match node_id
+        .as_str()
+        .map(|it| it.to_string())
+        .or_else(|| node_id.as_usize().map(|it| it.to_string()))
+    {
+        Some(node_id) => Some(node_id),
+        None if node_id.is_null() => None,
+        None => {
+            return BackendResponse::err_result("Field `node_id` must be a string or a number.");
+        }
+    };
+    let class = &query["class"];
+    if !class.is_null() && !class.is_string() {
+        return BackendResponse::err_result("Field `class` must be a string.");
+    }
+    let restriction = if let Some(node_id) = node_id {
+        Some(state.extract_tree_node_colors(&node_id))
     } else {
-        BackendResponse::err("No results found.")
+        class.as_str().map(|it| state.extract_class_colors(it))
+    };
+    let restriction = restriction.transpose().map_err(BackendResponse::err)?;
+
+    // The reachability itself can take a long time, hence it must not hold the lock.
+    let (graph, input_model, source, target) = {
+        let cmp_guard = state.attractor_computation_read();
+        let Some(cmp) = cmp_guard.as_ref() else {
+            return BackendResponse::err_result("No results available.");
+        };
+        let source = control::parse_subspace(&cmp.network, "source", &query["source"])
+            .map_err(BackendResponse::err)?;
+        let target = control::parse_subspace(&cmp.network, "target", &query["target"])
+            .map_err(BackendResponse::err)?;
+        (cmp.graph.clone(), cmp.input_model.clone(), source, target)
+    };
+    let colors = restriction.unwrap_or_else(|| graph.unit_colors().clone());
+    let universe = graph.unit_colored_vertices().intersect_colors(&colors);
+    let source_set = graph.mk_subspace(&source).intersect(&universe);
+    let target_set = graph.mk_subspace(&target).intersect(&universe);
+
+    let cancelled = || BackendResponse::err_result("Reachability query cancelled.");
+    // Target states reachable from the source, and source states that can reach the target.
+    let Some(forward) = forward_reach(&graph, &source_set, &universe, task) else {
+        return cancelled();
+    };
+    let Some(backward) = backward_reach(&graph, &target_set, &universe, task) else {
+        return cancelled();
+    };
+    let reachable_targets = forward.intersect(&target_set);
+    let reaching_sources = backward.intersect(&source_set);
+    let reachable_colors = reachable_targets.colors();
+    let mut result = object! {
+        "reachable" => !reachable_colors.is_empty(),
+        "cardinality" => reachable_colors.approx_cardinality(),
+        "target_state_count" => reachable_targets.vertices().exact_cardinality().to_string(),
+        "source_state_count" => reaching_sources.vertices().exact_cardinality().to_string(),
+    };
+    if reachable_colors.is_empty() {
+        return BackendResponse::ok_json_result(result);
     }
+
+    let witness_colour = reachable_colors.pick_singleton();
+    let Some(path) = find_path(
+        &graph,
+        &reaching_sources.intersect_colors(&witness_colour),
+        &reachable_targets.intersect_colors(&witness_colour),
+        task,
+    ) else {
+        return cancelled();
+    };
+    let path = path
+        .iter()
+        .map(|(state, variable)| {
+            object! {
+                "state" => state_to_json(&graph, state),
+                "variable" => variable.map(|it| graph.get_variable_name(it).clone()),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // The witness network includes the layout of the input model, which is only available
+    // as long as the computation did not change in the meantime.
+    let cmp_guard = state.attractor_computation_read();
+    let Some(cmp) = cmp_guard
+        .as_ref()
+        .filter(|cmp| cmp.input_model == input_model)
+    else {
+        return BackendResponse::err_result("Computation changed during the reachability query.");
+    };
+    let witness = graph.pick_witness(&witness_colour);
+    result["witness"] = witness_model_string(cmp, &witness).into();
+    result["path"] = path.into();
+    BackendResponse::ok_json_result(result)
+}
+
+/// Compute trap spaces (`minimal`, `maximal` or `essential`) of the model given in the
+/// request body, for all parametrizations.
+///
+/// This does not require a finished attractor computation and can be used to quickly
+/// pre-screen a model. However, if the session has a finished computation for the same
+/// model, each trap space also lists the attractors that it contains.
+#[post("/get_trap_spaces/<kind>?<limit>", format = "plain", data = "<data>")]
+async fn get_trap_spaces(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    data: Data<'_>,
+    kind: &str,
+    limit: Option<usize>,
+) -> BackendResult {
+    let state = storage.get_with(key, Default::default);
+    let kind = TrapSpaceKind::try_from(kind).map_err(BackendResponse::err)?;
+    let limit = limit.unwrap_or(100).min(MAX_TRAP_SPACE_COUNT);
+    let aeon_string = load_string(data, MAX_MODEL_SIZE).await?;
+    tokio::task::spawn_blocking(move || {
+        let network =
+            BooleanNetwork::try_from(aeon_string.as_str()).map_err(BackendResponse::err)?;
+        let trap_spaces = TrapSpaceSet::compute(&network, kind).map_err(BackendResponse::err)?;
+
+        let cmp_guard = state.attractor_computation_read();
+        let cmp = cmp_guard
+            .as_ref()
+            .filter(|cmp| cmp.thread.is_none() && cmp.input_model == aeon_string);
+        let graph = match cmp {
+            Some(cmp) => cmp.graph.clone(),
+            None => SymbolicAsyncGraph::new(&network).map_err(BackendResponse::err)?,
+        };
+        let components = cmp.map(|cmp| cmp.classifier.export_components());
+
+        let spaces = trap_spaces
+            .enumerate(&graph, limit)
+            .into_iter()
+            .map(|space| {
+                let mut result = space.to_json(&graph);
+                if let Some(components) = &components {
+                    let attractors = space
+                        .contained_attractors(&graph, components)
+                        .into_iter()
+                        .map(|(id, behaviour)| {
+                            let mut classes = JsonValue::new_object();
+                            for (b, colors) in behaviour {
+                                classes
+                                    .insert(&format!("{:?}", b), colors.approx_cardinality())
+                                    .unwrap();
+                            }
+                            object! { "id" => id, "behaviour" => classes }
+                        })
+                        .collect::<Vec<_>>();
+                    result.insert("attractors", attractors).unwrap();
+                }
+                result
+            })
+            .collect::<Vec<_>>();
+
+        BackendResponse::ok_json_result(object! {
+            "kind" => kind.to_string(),
+            "space_count" => trap_spaces.approx_space_count(),
+            "cardinality" => trap_spaces.approx_cardinality(),
+            "has_attractors" => components.is_some(),
+            "spaces" => spaces,
+        })
+    })
+    .await?
+}
+
+/// Compute fixed points (sink states) of the model given in the request body directly,
+/// without running the attractor search.
+///
+/// The fixed points are projected onto `variables` (comma-separated names, all variables
+/// by default) and at most `limit` distinct projected valuations are returned.
+#[post(
+    "/get_fixed_points?<limit>&<variables>",
+    format = "plain",
+    data = "<data>"
+)]
+async fn get_fixed_points(
+    data: Data<'_>,
+    limit: Option<usize>,
+    variables: Option<String>,
+) -> BackendResult {
+    let aeon_string = load_string(data, MAX_MODEL_SIZE).await?;
+    tokio::task::spawn_blocking(move || {
+        let network =
+            BooleanNetwork::try_from(aeon_string.as_str()).map_err(BackendResponse::err)?;
+        let graph = SymbolicAsyncGraph::new(&network).map_err(BackendResponse::err)?;
+        let variables = parse_variable_list(&graph, variables)?;
+        let fixed_points = fixed_points(&graph, graph.unit_colored_vertices());
+        let projection = Projection::new(&graph, fixed_points, variables);
+        Ok(projection_response(&graph, projection, limit))
+    })
+    .await?
+}
+
+/// **(internal)** Enumerate at most `limit` items of the given projection and convert them
+/// to a JSON response (including the size of the whole projected set).
+fn projection_response(
+    graph: &SymbolicAsyncGraph,
+    projection: Projection<'_>,
+    limit: Option<usize>,
+) -> BackendResponse {
+    let limit = limit.unwrap_or(100).min(MAX_PROJECTION_COUNT);
+    let cardinality = projection.remaining().colors().approx_cardinality();
+    // Exact cardinality can overflow any JSON number.
+    let state_count = projection
+        .remaining()
+        .vertices()
+        .exact_cardinality()
+        .to_string();
+    let variables = projection
+        .variables()
+        .iter()
+        .map(|var| graph.get_variable_name(*var).clone())
+        .collect::<Vec<_>>();
+    let mut items = projection.take(limit + 1).collect::<Vec<_>>();
+    let is_truncated = items.len() > limit;
+    items.truncate(limit);
+    BackendResponse::ok_json(object! {
+        "variables" => variables,
+        "cardinality" => cardinality,
+        "state_count" => state_count,
+        "is_truncated" => is_truncated,
+        "items" => items.iter().map(|it| it.to_json(graph)).collect::<Vec<_>>(),
+    })
+}
+
+/// **(internal)** Parse a comma-separated list of variable names. If no list is given,
+/// all network variables are returned.
+fn parse_variable_list(
+    graph: &SymbolicAsyncGraph,
+    names: Option<String>,
+) -> Result<Vec<VariableId>, BackendResponse> {
+    let Some(names) = names else {
+        return Ok(graph.variables().collect());
+    };
+    let mut result = Vec::new();
+    for name in names
+        .split(',')
+        .map(|it| it.trim())
+        .filter(|it| !it.is_empty())
+    {
+        let Some(var) = graph.symbolic_context().find_network_variable(name) else {
+            return Err(BackendResponse::err(format!(
+                "Unknown graph variable `{}`.",
+                name
+            )));
+        };
+        result.push(var);
+    }
+    Ok(result)
 }
 
 #[post("/cancel_control_computation")]
@@ -1081,8 +2547,11 @@ async fn cancel_control_computation(
     BackendResponse::ok("Cancelled")
 }
 
+/// Start a control computation configured using `#!control` model annotations.
+///
+/// This is kept for compatibility; prefer `/start_control` which accepts a JSON request.
 #[post(
-    "/start_control_computation/<oscillation>/<min_robustness>/<max_size>/<result_count>",
+    "/start_control_computation/<oscillation>/<min_robustness>/<max_size>/<result_count>?<mode>&<quantifier>",
     format = "plain",
     data = "<data>"
 )]
@@ -1092,78 +2561,72 @@ async fn start_control_computation(
     data: Data<'_>,
     // Type of oscillation that is admissible for the phenotype (allowed, required, forbidden).
     oscillation: &str,
-    // Only compute perturbations with robustness greater than this.
+    // Only report perturbations with robustness at least this (between 0 and 1).
     min_robustness: f64,
     // Only compute perturbations with at most this many perturbed variables.
     max_size: usize,
-    // Limit the number of results.
+    // Stop the computation once this many perturbations are found.
     result_count: usize,
+    // Control mode (permanent, temporary, reprogramming); permanent by default.
+    mode: Option<&str>,
+    // Whether all or any attractor must satisfy the phenotype (all, any); all by default.
+    quantifier: Option<&str>,
 ) -> BackendResult {
     let state = storage.get_with(key, Default::default);
-
-    // This method:
-    // 1. Reads a BN from the data stream.
-    // 2. Reads algorithm configuration from arguments.
-    // 3. Reads perturbable variables from model annotations.
-    // 4. Starts a new computation thread assuming a computation isn't running yet.
-
-    // These two values are unused for now. We need to add support for this into the Rust dependency.
-    assert!(result_count > 0);
-    assert!(min_robustness >= 0.0);
-
     let aeon_string = load_string(data, MAX_MODEL_SIZE).await?;
-    let network = BooleanNetwork::try_from(aeon_string.as_str()).map_err(BackendResponse::err)?;
+    let request = ControlRequest::from_annotations(
+        aeon_string,
+        mode.unwrap_or("permanent"),
+        quantifier.unwrap_or("all"),
+        oscillation,
+        min_robustness,
+        max_size,
+        result_count,
+    )
+    .map_err(BackendResponse::err)?;
+    start_control(&state, request)
+}
 
-    // Read the input for the control procedure.
-    let network_annotations = ModelAnnotation::from_model_string(aeon_string.as_str());
-    let mut controllable_vars = Vec::new();
-    let mut phenotype_subspace = Vec::new();
-    for var in network.variables() {
-        let name = network.get_variable_name(var);
-        if let Some(value) = network_annotations.get_value(&["control", name.as_str()]) {
-            let mut vals = value.split(",");
-            let is_controllable = vals.next();
-            let phenotype_value = vals.next();
-            if vals.next().is_some() {
-                return BackendResponse::err_result(format!(
-                    "Invalid control annotation for variable {name}: {value}"
-                ));
-            }
-            match is_controllable {
-                Some("true") => controllable_vars.push(var),
-                Some("false") => (),
-                _ => {
-                    return BackendResponse::err_result(format!(
-                        "Invalid control annotation for variable {name}: {value}"
-                    ));
-                }
-            }
-            match phenotype_value {
-                Some("true") => phenotype_subspace.push((var, true)),
-                Some("false") => phenotype_subspace.push((var, false)),
-                Some("null") => (),
-                _ => {
-                    return BackendResponse::err_result(format!(
-                        "Invalid control annotation for variable {name}: {value}"
-                    ));
-                }
-            }
-        }
-    }
+/// Start a control computation described by a JSON request (see `ControlRequest::from_json`).
+#[post("/start_control", format = "plain", data = "<data>")]
+async fn start_control_json(
+    key: SessionKey,
+    storage: &State<SessionStorage>,
+    data: Data<'_>,
+) -> BackendResult {
+    let state = storage.get_with(key, Default::default);
+    let request = load_string(data, MAX_MODEL_SIZE).await?;
+    let request = json::parse(&request)?;
+    let request = ControlRequest::from_json(&request).map_err(BackendResponse::err)?;
+    start_control(&state, request)
+}
 
-    let oscillation = match oscillation {
-        "required" => PhenotypeOscillationType::Required,
-        "forbidden" => PhenotypeOscillationType::Forbidden,
-        "allowed" => PhenotypeOscillationType::Allowed,
-        _ => {
-            return BackendResponse::err_result(format!(
-                "Invalid oscillation value {oscillation}."
-            ));
-        }
-    };
+/// Start a new control computation thread, assuming a computation isn't running yet.
+fn start_control(state: &Arc<SessionState>, request: ControlRequest) -> BackendResult {
+    println!(
+        "Phenotype: {} ({})",
+        request.phenotype_expression, request.quantifier
+    );
+    println!("Controllable variables: {:?}", request.controllable_names());
+
+    // Fail early, before the (possibly expensive) perturbation graph is created. The check is
+    // repeated once the write lock is acquired.
+    if state
+        .control_computation_read()
+        .as_ref()
+        .is_some_and(|cmp| cmp.thread.is_some())
+    {
+        return BackendResponse::err_result("Previous computation is still running.");
+    }
 
-    println!("Phenotype subset: {:?}", phenotype_subspace);
-    println!("Controllable variables: {:?}", controllable_vars);
+    let restriction = request.restriction.clone();
+    let mut computation = ControlComputation::new(request);
+    if let Some(restriction) = restriction {
+        let colors = state
+            .extract_control_restriction(&computation, &restriction)
+            .map_err(BackendResponse::err)?;
+        computation.restriction = Some(colors);
+    }
 
     let mut cmp_guard = state.control_computation_write();
 
@@ -1173,63 +2636,19 @@ async fn start_control_computation(
         return BackendResponse::err_result("Previous computation is still running.");
     }
 
-    let pstg = PerturbationGraph::with_restricted_variables(&network, controllable_vars);
-
-    let cancel_flag = cancel_this::CancelAtomic::new();
-    let mut computation = ControlComputation {
-        timestamp: SystemTime::now(),
-        finished_timestamp: None,
-        input_model: aeon_string,
-        graph: pstg,
-        thread: None,
-        results: None,
-        is_cancelled: cancel_flag.clone(),
-    };
-
     let thread_state = state.clone();
     computation.thread = Some(std::thread::spawn(move || {
-        let results = if let Some(cmp) = thread_state.control_computation_read().as_ref() {
-            let pstg = &cmp.graph;
-
-            let phenotype = pstg
-                .as_original()
-                .mk_subspace(&phenotype_subspace)
-                .vertices();
-
-            let mut admissible_perturbations = pstg.mk_empty_colors();
-            for i in 0..=max_size {
-                let size_perturbations = pstg.create_perturbation_colors(i, true);
-                admissible_perturbations = admissible_perturbations.union(&size_perturbations);
-            }
-
-            let results = pstg.phenotype_permanent_control_iterated(
-                &phenotype,
-                oscillation,
-                Some(&admissible_perturbations),
-                pstg.mk_unit_colored_vertices().vertices(),
-                Some(min_robustness),
-                Some(result_count),
-                true,
-                |partial| {
-                    if cancel_flag.is_cancelled() {
-                        Err(partial.clone())
-                    } else {
-                        Ok(())
-                    }
-                },
-            );
-
-            results.unwrap_or_else(|partial| partial)
+        if let Some(cmp) = thread_state.control_computation_read().as_ref() {
+            cmp.run();
         } else {
             panic!("Computation disappeared.");
-        };
+        }
 
         let mut cmp_guard = thread_state.control_computation_write();
         let Some(cmp) = cmp_guard.as_mut() else {
             panic!("Computation disappeared.");
         };
 
-        cmp.results = Some(results);
         cmp.finished_timestamp = Some(SystemTime::now());
         cmp.thread = None;
     }));
@@ -1274,6 +2693,7 @@ async fn start_computation(
         task,
         input_model: aeon_string.clone(),
         classifier: Classifier::new(&graph),
+        network,
         graph,
         thread: None,
         finished_timestamp: None,
@@ -1288,61 +2708,28 @@ async fn start_computation(
         // Note that this holds the read-lock for the whole duration of the computation. However,
         // since cancellation is possible with only a read lock, this is fine, and it guarantees
         // a new computation can never be created unless the previous one is canceled.
-        if let Some(cmp) = thread_state.attractor_computation_read().as_ref() {
+        let root_params = if let Some(cmp) = thread_state.attractor_computation_read().as_ref() {
             let task_context = &cmp.task;
             let graph = &cmp.graph;
+            let network = &cmp.network;
             let classifier = &cmp.classifier;
 
             // Upon cancellation, the code should continue normally with partial results
             // (i.e., anything that was computed before cancellation).
-            let _: Cancellable<()> = cancel_this::on_trigger(cmp.task.is_cancelled.clone(), || {
-                // First, perform ITGR reduction.
-                let state = ItgrState::new(graph, graph.unit_colored_vertices());
-                let mut itgr = InterleavedTransitionGuidedReduction::configure(graph, state);
-                let universe = loop {
-                    match itgr.try_compute() {
-                        Ok(result) => break result,
-                        Err(Incomplete::Cancelled(c)) => return Err(c),
-                        Err(Incomplete::Suspended) => {
-                            task_context.update_remaining(itgr.state().remaining());
-                        }
-                        Err(e) => panic!("{}", e),
-                    }
-                };
-
-                let active_variables = itgr.state().active_variables().collect::<BTreeSet<_>>();
-
-                // Then run Xie-Beerel to actually detect the components.
-                let mut config = AttractorConfig::new(graph.clone());
-                config.active_variables = active_variables;
-                let mut attractors = XieBeerelAttractors::configure(config, universe);
-
-                loop {
-                    match attractors.try_next() {
-                        None => break,
-                        Some(Ok(component)) => {
-                            println!("Component {}", component.approx_cardinality());
-                            cmp.task.increment_result_count();
-                            classifier.add_component(component, graph);
-                        }
-                        Some(Err(Incomplete::Cancelled(e))) => return Err(e),
-                        Some(Err(Incomplete::Suspended)) => {
-                            task_context.update_remaining(attractors.state().remaining());
-                        }
-                        Some(Err(e)) => panic!("{}", e),
-                    }
-                }
-
-                Ok(())
+            let _: Cancellable<()> = attractor_search(graph, task_context, |component| {
+                println!("Component {}", component.approx_cardinality());
+                classifier.add_component(component, graph);
             });
 
             println!("Component search done...");
 
             // Once computation is complete, we can convert the result into a decision tree:
             let result = classifier.export_result();
-            let mut tree = thread_state.bifurcation_tree_write();
-            *tree = Some(Bdt::new_from_graph(result, graph, &network));
+            let new_tree = Bdt::new_from_graph(result, graph, network);
+            let root_params = new_tree.all_node_params(new_tree.root_id());
+            *thread_state.bifurcation_tree_write() = Some(new_tree);
             println!("Saved decision tree");
+            root_params
         } else {
             panic!("Computation disappeared!");
         };
@@ -1354,16 +2741,84 @@ async fn start_computation(
         } else {
             panic!("Computation disappeared!");
         }
+
+        // Precompute stability of the tree root for all variables, so that the stability
+        // endpoints can respond immediately. The data is copied first, so that we do not
+        // block a new computation from starting. This is skipped if the computation was
+        // cancelled (then the results are typically incomplete anyway).
+        let cache = &thread_state.stability_cache;
+        let precompute_input = thread_state
+            .attractor_computation_read()
+            .as_ref()
+            .filter(|cmp| !cmp.task.is_cancelled())
+            .map(|cmp| {
+                let components = STABILITY_BEHAVIOURS.map(|behaviour| {
+                    let components = cmp
+                        .classifier
+                        .export_components_with_optional_class(behaviour);
+                    (behaviour, components)
+                });
+                (cache.generation(), cmp.graph.clone(), components)
+            });
+        if let Some((generation, graph, components)) = precompute_input {
+            precompute_stability(cache, generation, &graph, &components, &root_params);
+        }
     });
 
     new_cmp.thread = Some(cmp_thread);
 
-    // Now write the new computation to the global state...
+    // Now write the new computation to the global state (cached results are no longer valid)...
     *cmp = Some(new_cmp);
+    state.stability_cache.clear();
 
     BackendResponse::ok_json_result(object! { "timestamp" => start })
 }
 
+/// Behaviour filters of the stability endpoints (`None` stands for all attractors).
+const STABILITY_BEHAVIOURS: [Option<Behaviour>; 4] = [
+    None,
+    Some(Behaviour::Stability),
+    Some(Behaviour::Oscillation),
+    Some(Behaviour::Disorder),
+];
+
+/// Fill the stability cache with stability data of all variables for the given colours
+/// (typically the root of the decision tree), for all behaviour filters.
+///
+/// The components of each behaviour are given by
+/// `Classifier::export_components_with_optional_class`. Stops early if the cache is cleared
+/// after `generation` (i.e. a new computation started).
+fn precompute_stability(
+    cache: &StabilityCache,
+    generation: usize,
+    graph: &SymbolicAsyncGraph,
+    components: &[(Option<Behaviour>, Vec<GraphColoredVertices>)],
+    colors: &GraphColors,
+) {
+    for (behaviour, components) in components {
+        let attractors = components
+            .iter()
+            .filter_map(|attractor| {
+                attractor
+                    .intersect_colors(colors)
+                    .take_if(|it| !it.is_empty())
+            })
+            .collect::<Vec<_>>();
+        if attractors.is_empty() {
+            continue;
+        }
+        for variable in graph.variables() {
+            if cache.generation() != generation {
+                return;
+            }
+            if cache.get(colors, *behaviour, variable).is_none() {
+                let stability = VariableStability::for_attractors(graph, &attractors, variable);
+                cache.insert(generation, colors, *behaviour, variable, stability);
+            }
+        }
+    }
+}
+
 #[post("/cancel_computation", format = "plain")]
 fn cancel_computation(key: SessionKey, storage: &State<SessionStorage>) -> BackendResponse {
     let state = storage.get_with(key, Default::default);
@@ -1518,18 +2973,38 @@ fn rocket() -> _ {
                 cancel_computation,
                 get_results,
                 get_witness,
+                get_witnesses,
                 get_tree_witness,
+                get_tree_witnesses,
                 get_attractors,
                 get_tree_attractors,
+                get_basins,
+                get_tree_basins,
+                cancel_basins,
+                reachability,
+                cancel_reachability,
+                get_trap_spaces,
+                get_fixed_points,
+                get_projected_attractors,
+                get_tree_projected_attractors,
+                get_stability_projected_attractors,
                 get_stability_data,
                 get_stability_attractors,
                 get_stability_witness,
+                get_stability_witnesses,
+                get_joint_stability_data,
+                get_joint_stability_witness,
+                get_joint_stability_attractors,
+                get_stability_profiles,
+                get_stability_profile_witness,
+                get_stability_profile_attractors,
                 check_update_function,
                 sbml_to_aeon,
                 aeon_to_sbml,
                 aeon_to_sbml_instantiated,
                 get_bifurcation_tree,
                 get_attributes,
+                get_attribute_ranking,
                 apply_attribute,
                 revert_decision,
                 apply_tree_precision,
@@ -1537,16 +3012,24 @@ fn rocket() -> _ {
                 auto_expand,
                 all_options,
                 start_control_computation,
+                start_control_json,
                 cancel_control_computation,
                 get_control_computation_status,
                 get_control_results,
                 get_control_stats,
+                get_control_perturbation,
+                cancel_control_perturbation,
+                export_control_results,
+                compare_control_results,
             ],
         )
 }
 
 type BackendResult = Result<BackendResponse, BackendResponse>;
 
+/// Result of endpoints that can also respond with a downloadable file.
+type ExportResult = Result<ExportResponse, BackendResponse>;
+
 struct BackendResponse {
     message: String,
 }
@@ -1600,13 +3083,61 @@ impl<'r, 's: 'r> Responder<'r, 's> for BackendResponse {
     fn respond_to(self, _request: &'r Request<'_>) -> response::Result<'s> {
         use std::io::Cursor;
 
-        Response::build()
+        cors_response()
             .header(ContentType::Plain)
-            // This magic set of headers might fix some CORS issues, but we are not sure yet...
-            .header(Header::new("Allow", "GET, POST, OPTIONS, PUT, DELETE"))
-            .header(Header::new("Access-Control-Allow-Methods", "GET, POST, OPTIONS, PUT, DELETE"))
-            .header(Header::new("Access-Control-Allow-Headers", "X-API-KEY, Origin, X-Requested-With, Content-Type, Accept, Access-Control-Request-Method"))
             .sized_body(self.message.len(), Cursor::new(self.message))
             .ok()
     }
 }
+
+/// **(internal)** Response builder with the headers shared by all backend responses.
+fn cors_response<'r>() -> response::Builder<'r> {
+    let mut builder = Response::build();
+    builder
+        // This magic set of headers might fix some CORS issues, but we are not sure yet...
+        .header(Header::new("Allow", "GET, POST, OPTIONS, PUT, DELETE"))
+        .header(Header::new("Access-Control-Allow-Methods", "GET, POST, OPTIONS, PUT, DELETE"))
+        .header(Header::new("Access-Control-Allow-Headers", "X-API-KEY, Origin, X-Requested-With, Content-Type, Accept, Access-Control-Request-Method"));
+    builder
+}
+
+/// A downloadable file, e.g. a CSV table (sent without the JSON status envelope).
+struct FileResponse {
+    file_name: String,
+    content_type: ContentType,
+    content: String,
+}
+
+impl<'r, 's: 'r> Responder<'r, 's> for FileResponse {
+    fn respond_to(self, _request: &'r Request<'_>) -> response::Result<'s> {
+        use std::io::Cursor;
+
+        let disposition = format!("attachment; filename=\"{}\"", self.file_name);
+        cors_response()
+            .header(self.content_type)
+            .header(Header::new("Content-Disposition", disposition))
+            .sized_body(self.content.len(), Cursor::new(self.content))
+            .ok()
+    }
+}
+
+/// Exported results, either as a downloadable file or as a regular JSON response.
+enum ExportResponse {
+    File(FileResponse),
+    Json(BackendResponse),
+}
+
+impl From<BackendResponse> for ExportResponse {
+    fn from(value: BackendResponse) -> Self {
+        ExportResponse::Json(value)
+    }
+}
+
+impl<'r, 's: 'r> Responder<'r, 's> for ExportResponse {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'s> {
+        match self {
+            ExportResponse::File(file) => file.respond_to(request),
+            ExportResponse::Json(json) => json.respond_to(request),
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use crate::scc::{AttractorEdge, AttractorGraph, AttractorQuotient, AttractorSummary, Behaviour};
+use crate::util::functional::Functional;
+use biodivine_lib_param_bn::VariableId;
+use biodivine_lib_param_bn::biodivine_std::bitvector::{ArrayBitVector, BitVector};
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColoredVertices, SymbolicAsyncGraph};
+use json::JsonValue;
+use std::collections::HashMap;
+
+impl AttractorGraph {
+    /// Compute the state graph of the given attractor.
+    ///
+    /// The attractor should contain a single colour (see `AttractorSummary::compute`). If it
+    /// has at least `max_states` states, the explicit graph is replaced by a summary with
+    /// `samples` sampled states and an SCC quotient over `quotient_variables` (if not empty).
+    ///
+    /// Returns an error if the quotient cannot be computed (see `AttractorQuotient::compute`).
+    pub fn compute(
+        graph: &SymbolicAsyncGraph,
+        attractor: &GraphColoredVertices,
+        behaviour: Behaviour,
+        max_states: usize,
+        samples: usize,
+        quotient_variables: &[VariableId],
+    ) -> Result<AttractorGraph, String> {
+        let is_large = attractor.vertices().approx_cardinality() >= max_states as f64;
+        if is_large {
+            let mut summary = AttractorSummary::compute(graph, attractor, samples);
+            if !quotient_variables.is_empty() {
+                let variables = quotient_variables.to_vec();
+                summary.quotient = Some(AttractorQuotient::compute(graph, attractor, variables)?);
+            }
+            return Ok(AttractorGraph {
+                behaviour,
+                subspace: summary.subspace.clone(),
+                state_count: summary.state_count.clone(),
+                states: Vec::new(),
+                edges: Vec::new(),
+                summary: Some(summary),
+            });
+        }
+
+        let colors = attractor.colors();
+        let states = attractor
+            .vertices()
+            .materialize()
+            .iter()
+            .collect::<Vec<ArrayBitVector>>();
+        let index = states
+            .iter()
+            .enumerate()
+            .map(|(i, state)| (state.clone(), i))
+            .collect::<HashMap<_, _>>();
+
+        let mut edges = Vec::new();
+        for (source, state) in states.iter().enumerate() {
+            let source_set = graph.vertex(state).intersect_colors(&colors);
+            for variable in graph.variables() {
+                let post = graph.var_post(variable, &source_set);
+                let target = post.vertices().materialize().iter().next();
+                if let Some(target) = target.and_then(|it| index.get(&it)) {
+                    edges.push(AttractorEdge {
+                        source,
+                        variable,
+                        target: *target,
+                    });
+                }
+            }
+        }
+
+        let subspace = graph
+            .variables()
+            .map(|var| {
+                let mut values = states.iter().map(|state| state.get(var.to_index()));
+                let first = values.next()?;
+                if values.all(|it| it == first) {
+                    Some(first)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Ok(AttractorGraph {
+            behaviour,
+            subspace,
+            state_count: attractor.vertices().exact_cardinality(),
+            states,
+            edges,
+            summary: None,
+        })
+    }
+
+    /// True if the attractor was too large to be represented explicitly.
+    pub fn is_large(&self) -> bool {
+        self.summary.is_some()
+    }
+
+    /// Variables which can change their value in this attractor.
+    pub fn free_variables(&self) -> Vec<VariableId> {
+        self.subspace
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| value.is_none())
+            .map(|(i, _)| VariableId::from_index(i))
+            .collect()
+    }
+
+    /// Variables that can be updated in the given state (i.e. labels of its outgoing edges).
+    pub fn enabled_variables(&self, state: usize) -> Vec<VariableId> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.source == state)
+            .map(|edge| edge.variable)
+            .collect()
+    }
+
+    /// Convert this attractor graph to JSON (see `SCHEMA_VERSION` of the server).
+    ///
+    /// States are objects mapping variable names to Boolean values, edges reference states
+    /// by their index and carry the name of the updated variable. The names of variables
+    /// enabled in each state are given in `enabled_variables` (indexed like `states`).
+    pub fn to_json(&self, graph: &SymbolicAsyncGraph) -> JsonValue {
+        let mut fixed_variables = JsonValue::new_object();
+        for (var, value) in graph.variables().zip(self.subspace.iter()) {
+            if let Some(value) = value {
+                fixed_variables
+                    .insert(&graph.get_variable_name(var), *value)
+                    .unwrap();
+            }
+        }
+        let states = self
+            .states
+            .iter()
+            .map(|state| state_to_json(graph, state))
+            .collect::<Vec<_>>();
+        let enabled_variables = (0..self.states.len())
+            .map(|state| {
+                self.enabled_variables(state)
+                    .into_iter()
+                    .map(|var| graph.get_variable_name(var).clone())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let edges = self
+            .edges
+            .iter()
+            .map(|edge| {
+                object! {
+                    "source" => edge.source,
+                    "target" => edge.target,
+                    "variable" => graph.get_variable_name(edge.variable).clone(),
+                }
+            })
+            .collect::<Vec<_>>();
+        object! {
+            "behaviour" => format!("{:?}", self.behaviour),
+            "state_count" => self.state_count.to_string(),
+            "fixed_variables" => fixed_variables,
+            "is_large" => self.is_large(),
+            "states" => states,
+            "enabled_variables" => enabled_variables,
+            "edges" => edges,
+            "summary" => self.summary.as_ref().map(|it| it.to_json(graph)),
+        }
+    }
+
+    /// Write this attractor as a Graphviz `subgraph` cluster (see `attractors_to_dot`).
+    ///
+    /// Nodes are identified as `a<id>_s<index>` and labelled with the values of all variables.
+    /// Large attractors are written as a single node labelled with their subspace
+    /// (`-` marks free variables) and state count.
+    pub fn to_dot(&self, graph: &SymbolicAsyncGraph, id: usize) -> String {
+        let mut dot = format!(
+            "  subgraph cluster_{} {{\n    label=\"{:?}\";\n",
+            id, self.behaviour
+        );
+        if self.is_large() {
+            let subspace = self
+                .subspace
+                .iter()
+                .map(|value| match value {
+                    Some(true) => '1',
+                    Some(false) => '0',
+                    None => '-',
+                })
+                .collect::<String>();
+            dot += &format!(
+                "    a{}_subspace [shape=box, label=\"{}\\n{} states\"];\n",
+                id, subspace, self.state_count
+            );
+        } else {
+            for (index, state) in self.states.iter().enumerate() {
+                let label = graph
+                    .variables()
+                    .map(|var| if state.get(var.to_index()) { '1' } else { '0' })
+                    .collect::<String>();
+                dot += &format!("    a{}_s{} [label=\"{}\"];\n", id, index, label);
+            }
+            for edge in &self.edges {
+                dot += &format!(
+                    "    a{}_s{} -> a{}_s{} [label=\"{}\"];\n",
+                    id,
+                    edge.source,
+                    id,
+                    edge.target,
+                    graph.get_variable_name(edge.variable)
+                );
+            }
+        }
+        dot + "  }\n"
+    }
+}
+
+/// Export the given attractors as one Graphviz `digraph`, with one cluster per attractor.
+///
+/// The order of variables in state labels is given in the graph comment.
+pub fn attractors_to_dot(graph: &SymbolicAsyncGraph, attractors: &[AttractorGraph]) -> String {
+    let variables = graph
+        .variables()
+        .map(|var| graph.get_variable_name(var).clone())
+        .collect::<Vec<_>>();
+    let mut dot = format!(
+        "digraph attractors {{\n  comment=\"{}\";\n",
+        variables.join(",")
+    );
+    for (id, attractor) in attractors.iter().enumerate() {
+        dot += &attractor.to_dot(graph, id);
+    }
+    dot + "}\n"
+}
+
+/// Convert a state to a JSON object which maps variable names to Boolean values.
+pub fn state_to_json(graph: &SymbolicAsyncGraph, state: &ArrayBitVector) -> JsonValue {
+    JsonValue::new_object().apply(|object| {
+        for var in graph.variables() {
+            object
+                .insert(&graph.get_variable_name(var), state.get(var.to_index()))
+                .unwrap();
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scc::{AttractorGraph, Behaviour, attractors_to_dot};
+    use biodivine_lib_param_bn::BooleanNetwork;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use std::convert::TryFrom;
+
+    // `A` oscillates and `B` follows `A`: one cyclic attractor `00 -> 10 -> 11 -> 01 -> 00`.
+    const MODEL: &str = "A -| A\nA -> B\n$A: !A\n$B: A\n";
+
+    #[test]
+    fn attractor_graph_json() {
+        let network = BooleanNetwork::try_from(MODEL).unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let attractor = graph.mk_unit_colored_vertices();
+
+        let explicit =
+            AttractorGraph::compute(&graph, &attractor, Behaviour::Oscillation, 500, 2, &[])
+                .unwrap();
+        let json = explicit.to_json(&graph);
+        assert_eq!(json["behaviour"], "Oscillation");
+        assert_eq!(json["state_count"], "4");
+        assert_eq!(json["is_large"], false);
+        assert_eq!(json["states"].len(), 4);
+        assert_eq!(json["edges"].len(), 4);
+        assert!(json["fixed_variables"].is_empty());
+        assert!(json["summary"].is_null());
+
+        // Attractors with exactly `max_states` states are already considered large.
+        let large =
+            AttractorGraph::compute(&graph, &attractor, Behaviour::Oscillation, 4, 2, &[]).unwrap();
+        let json = large.to_json(&graph);
+        assert_eq!(json["is_large"], true);
+        assert!(json["states"].is_empty());
+        assert_eq!(json["summary"]["state_count"], "4");
+        assert_eq!(json["summary"]["sampled_states"].len(), 2);
+        assert_eq!(json["summary"]["stability"]["A"], "unstable");
+    }
+
+    #[test]
+    fn attractor_transitions_dot() {
+        let network = BooleanNetwork::try_from(MODEL).unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let a = network.as_graph().find_variable("A").unwrap();
+        let attractor = graph.mk_unit_colored_vertices();
+        let explicit =
+            AttractorGraph::compute(&graph, &attractor, Behaviour::Oscillation, 500, 2, &[])
+                .unwrap();
+
+        // Every state has exactly one enabled variable, alternating between `A` and `B`.
+        for state in 0..explicit.states.len() {
+            assert_eq!(explicit.enabled_variables(state).len(), 1);
+        }
+        assert_eq!(
+            explicit.edges.iter().filter(|it| it.variable == a).count(),
+            2
+        );
+
+        let dot = attractors_to_dot(&graph, &[explicit]);
+        assert!(dot.starts_with("digraph attractors {\n  comment=\"A,B\";\n"));
+        assert_eq!(dot.matches(" -> ").count(), 4);
+        assert_eq!(dot.matches("[label=\"A\"]").count(), 2);
+        assert!(dot.contains("a0_s0 [label="));
+    }
+}
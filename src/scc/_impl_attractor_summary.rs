@@ -0,0 +1,277 @@
+use crate::GraphTaskContext;
+use crate::scc::_impl_attractor_graph::state_to_json;
+use crate::scc::algo_reachability::{backward_reach, forward_reach};
+use crate::scc::{AttractorQuotient, AttractorSummary, MAX_QUOTIENT_NODES, MAX_QUOTIENT_VARIABLES};
+use biodivine_lib_param_bn::VariableId;
+use biodivine_lib_param_bn::biodivine_std::bitvector::ArrayBitVector;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColoredVertices, SymbolicAsyncGraph};
+use json::JsonValue;
+
+impl AttractorSummary {
+    /// Compute a summary of the given attractor.
+    ///
+    /// The attractor should contain a single colour (i.e. it is an attractor of one witness
+    /// parametrization), otherwise the sampled transitions mix different parametrizations.
+    /// At most `sample_count` states are sampled. The `quotient` is not computed
+    /// (see `AttractorQuotient::compute`).
+    pub fn compute(
+        graph: &SymbolicAsyncGraph,
+        attractor: &GraphColoredVertices,
+        sample_count: usize,
+    ) -> AttractorSummary {
+        let subspace = graph
+            .variables()
+            .map(|var| {
+                let var_is_true = graph.fix_network_variable(var, true);
+                let can_be_true = !attractor.intersect(&var_is_true).is_empty();
+                let can_be_false = !attractor.minus(&var_is_true).is_empty();
+                match (can_be_true, can_be_false) {
+                    (true, false) => Some(true),
+                    (false, true) => Some(false),
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut sampled_states = Vec::new();
+        let mut remaining = attractor.clone();
+        while sampled_states.len() < sample_count && !remaining.is_empty() {
+            let vertex = remaining.pick_vertex();
+            remaining = remaining.minus(&vertex);
+            if let Some(state) = vertex.vertices().materialize().iter().next() {
+                sampled_states.push(state);
+            }
+        }
+
+        let attractor_colors = attractor.colors();
+        let mut sampled_transitions = Vec::new();
+        for state in &sampled_states {
+            let source = graph.vertex(state).intersect_colors(&attractor_colors);
+            for var in graph.variables() {
+                let post = graph.var_post(var, &source);
+                if let Some(target) = post.vertices().materialize().iter().next() {
+                    sampled_transitions.push((state.clone(), var, target));
+                }
+            }
+        }
+
+        AttractorSummary {
+            subspace,
+            state_count: attractor.vertices().exact_cardinality(),
+            sampled_states,
+            sampled_transitions,
+            quotient: None,
+        }
+    }
+
+    /// Convert this summary to JSON. States are exported as objects mapping variable names
+    /// to Boolean values, and the exact state count is exported as a string.
+    pub fn to_json(&self, graph: &SymbolicAsyncGraph) -> JsonValue {
+        let state_to_json = |state: &ArrayBitVector| state_to_json(graph, state);
+        let mut subspace = JsonValue::new_object();
+        let mut stability = JsonValue::new_object();
+        for (var, value) in graph.variables().zip(self.subspace.iter()) {
+            let name = graph.get_variable_name(var);
+            if let Some(value) = value {
+                subspace.insert(&name, *value).unwrap();
+            }
+            let var_stability = match value {
+                Some(true) => "true",
+                Some(false) => "false",
+                None => "unstable",
+            };
+            stability.insert(&name, var_stability).unwrap();
+        }
+        let states = self
+            .sampled_states
+            .iter()
+            .map(state_to_json)
+            .collect::<Vec<_>>();
+        let transitions = self
+            .sampled_transitions
+            .iter()
+            .map(|(source, var, target)| {
+                object! {
+                    "source" => state_to_json(source),
+                    "variable" => graph.get_variable_name(*var).clone(),
+                    "target" => state_to_json(target),
+                }
+            })
+            .collect::<Vec<_>>();
+        object! {
+            "subspace" => subspace,
+            "stability" => stability,
+            "state_count" => self.state_count.to_string(),
+            "sampled_states" => states,
+            "sampled_transitions" => transitions,
+            "quotient" => self.quotient.as_ref().map(|it| it.to_json(graph)),
+        }
+    }
+}
+
+impl AttractorQuotient {
+    /// Compute the SCC quotient of the attractor with respect to the given variables.
+    ///
+    /// The attractor is first split by its projection onto `variables`, and each such group
+    /// is decomposed into strongly connected components using only the transitions which stay
+    /// within the group (i.e. update the remaining variables). The components are the nodes of
+    /// the quotient, and the transitions updating `variables` are its edges.
+    ///
+    /// The attractor should contain a single colour. Returns an error if a variable is given
+    /// more than once, if more than `MAX_QUOTIENT_VARIABLES` variables are given, or if
+    /// the quotient has more than `MAX_QUOTIENT_NODES` nodes.
+    pub fn compute(
+        graph: &SymbolicAsyncGraph,
+        attractor: &GraphColoredVertices,
+        variables: Vec<VariableId>,
+    ) -> Result<AttractorQuotient, String> {
+        for (i, var) in variables.iter().enumerate() {
+            if variables[..i].contains(var) {
+                return Err(format!(
+                    "Variable `{}` is used more than once in the attractor quotient.",
+                    graph.get_variable_name(*var)
+                ));
+            }
+        }
+        if variables.len() > MAX_QUOTIENT_VARIABLES {
+            return Err(format!(
+                "The attractor quotient supports at most {} variables, but {} were given.",
+                MAX_QUOTIENT_VARIABLES,
+                variables.len()
+            ));
+        }
+
+        // The task is never cancelled: the quotient size is bounded by `MAX_QUOTIENT_NODES`.
+        let task = GraphTaskContext::new();
+        let mut nodes = Vec::new();
+        let mut components = Vec::new();
+        for valuation_id in 0..(1usize << variables.len()) {
+            let valuation = (0..variables.len())
+                .map(|i| (valuation_id >> i) & 1 == 1)
+                .collect::<Vec<_>>();
+            let subspace = variables
+                .iter()
+                .cloned()
+                .zip(valuation.iter().cloned())
+                .collect::<Vec<_>>();
+            // Transitions updating `variables` leave the group, hence reachability within
+            // the group only uses the remaining variables.
+            let mut group = attractor.intersect(&graph.mk_subspace(&subspace));
+            while !group.is_empty() {
+                if nodes.len() == MAX_QUOTIENT_NODES {
+                    return Err(format!(
+                        "The attractor quotient has more than {} nodes. Use fewer variables.",
+                        MAX_QUOTIENT_NODES
+                    ));
+                }
+                let pivot = group.pick_vertex();
+                let forward = forward_reach(graph, &pivot, &group, &task).expect("Not cancelled.");
+                let backward =
+                    backward_reach(graph, &pivot, &forward, &task).expect("Not cancelled.");
+                group = group.minus(&backward);
+                nodes.push((valuation.clone(), backward.vertices().exact_cardinality()));
+                components.push(backward);
+            }
+        }
+
+        let mut edges = Vec::new();
+        for (source_id, source) in components.iter().enumerate() {
+            for var in &variables {
+                let successors = graph.var_post(*var, source);
+                if successors.is_empty() {
+                    continue;
+                }
+                for (target_id, target) in components.iter().enumerate() {
+                    if !successors.intersect(target).is_empty() {
+                        edges.push((source_id, *var, target_id));
+                    }
+                }
+            }
+        }
+
+        Ok(AttractorQuotient {
+            variables,
+            nodes,
+            edges,
+        })
+    }
+
+    pub fn to_json(&self, graph: &SymbolicAsyncGraph) -> JsonValue {
+        let variables = self
+            .variables
+            .iter()
+            .map(|var| graph.get_variable_name(*var).clone())
+            .collect::<Vec<_>>();
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|(valuation, count)| {
+                object! {
+                    "valuation" => valuation.clone(),
+                    "state_count" => count.to_string(),
+                }
+            })
+            .collect::<Vec<_>>();
+        let edges = self
+            .edges
+            .iter()
+            .map(|(source, var, target)| {
+                object! {
+                    "source" => *source,
+                    "variable" => graph.get_variable_name(*var).clone(),
+                    "target" => *target,
+                }
+            })
+            .collect::<Vec<_>>();
+        object! {
+            "variables" => variables,
+            "nodes" => nodes,
+            "edges" => edges,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scc::{AttractorQuotient, MAX_QUOTIENT_VARIABLES};
+    use biodivine_lib_param_bn::BooleanNetwork;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn attractor_scc_quotient() {
+        // `A` oscillates and `B` follows `A`: the whole state space is one cycle
+        // `00 -> 10 -> 11 -> 01 -> 00` (states written as `AB`).
+        let network = BooleanNetwork::try_from("A -| A\nA -> B\n$A: !A\n$B: A\n").unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let a = network.as_graph().find_variable("A").unwrap();
+        let attractor = graph.mk_unit_colored_vertices();
+
+        // Within each value of `A`, `B` can only change one way, hence every state is
+        // a separate component, connected by the transitions of `A` in both directions.
+        let quotient = AttractorQuotient::compute(&graph, &attractor, vec![a]).unwrap();
+        assert_eq!(quotient.nodes.len(), 4);
+        assert!(
+            quotient
+                .nodes
+                .iter()
+                .all(|(_, count)| *count == 1u32.into())
+        );
+        assert_eq!(quotient.edges.len(), 4);
+        assert!(quotient.edges.iter().all(|(_, var, _)| *var == a));
+
+        let error = AttractorQuotient::compute(&graph, &attractor, vec![a, a]).unwrap_err();
+        assert!(error.contains("`A`"));
+
+        let model = (0..=MAX_QUOTIENT_VARIABLES)
+            .map(|i| format!("$V{i}: true\n"))
+            .collect::<String>();
+        let network = BooleanNetwork::try_from(model.as_str()).unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let too_many = network.variables().collect::<Vec<_>>();
+        let attractor = graph.mk_unit_colored_vertices();
+        let error = AttractorQuotient::compute(&graph, &attractor, too_many).unwrap_err();
+        assert!(error.contains(&MAX_QUOTIENT_VARIABLES.to_string()));
+    }
+}
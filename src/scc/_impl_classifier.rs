@@ -1,4 +1,6 @@
 use super::{Behaviour, Class, Classifier};
+use crate::scc::algo_projection::Projection;
+use biodivine_lib_param_bn::VariableId;
 use biodivine_lib_param_bn::biodivine_std::traits::Set;
 use biodivine_lib_param_bn::symbolic_async_graph::{
     GraphColoredVertices, GraphColors, GraphVertices, SymbolicAsyncGraph,
@@ -85,6 +87,38 @@ impl Classifier {
         }
     }
 
+    /// Union of all attractor states restricted to the given `colors`. If `behaviour` is
+    /// given, only attractors with this behaviour are considered.
+    pub fn attractor_states(
+        &self,
+        graph: &SymbolicAsyncGraph,
+        colors: &GraphColors,
+        behaviour: Option<Behaviour>,
+    ) -> GraphColoredVertices {
+        let mut result = graph.mk_empty_colored_vertices();
+        for component in self.export_components_with_optional_class(behaviour) {
+            result = result.union(&component.intersect_colors(colors));
+        }
+        result
+    }
+
+    /// Enumerate the attractor states (see `Classifier::attractor_states`) projected onto the
+    /// given `variables`. Each item contains the distinct projected valuation together with
+    /// all states and colours where it appears in some attractor.
+    pub fn project_attractors<'a>(
+        &self,
+        graph: &'a SymbolicAsyncGraph,
+        colors: &GraphColors,
+        behaviour: Option<Behaviour>,
+        variables: Vec<VariableId>,
+    ) -> Projection<'a> {
+        Projection::new(
+            graph,
+            self.attractor_states(graph, colors, behaviour),
+            variables,
+        )
+    }
+
     /// Static function to classify just one component and immediately obtain results.
     pub fn classify_component(
         component: &GraphColoredVertices,
@@ -230,3 +264,69 @@ impl Classifier {
         is_not_sink
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::scc::{Behaviour, Classifier};
+    use biodivine_lib_param_bn::BooleanNetwork;
+    use biodivine_lib_param_bn::biodivine_std::traits::Set;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use std::convert::TryFrom;
+
+    // While `C` is off, `A` oscillates and eventually sets the latch `B`, giving the cyclic
+    // attractor `B & !C`. Before the latch is set, `C` can switch on instead, which stops `A`
+    // and leads to the fixed point `!A & !B & C`.
+    const MODEL: &str = "A -| A\nC -| A\nA -> B\nB -> B\nC -| B\nA -| C\nB -| C\nC -> C\n\
+        $A: !A & !C\n$B: B | (A & !C)\n$C: !B & (C | !A)\n";
+
+    #[test]
+    fn projected_attractor_states() {
+        let network = BooleanNetwork::try_from(MODEL).unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let a = network.as_graph().find_variable("A").unwrap();
+        let b = network.as_graph().find_variable("B").unwrap();
+        let c = network.as_graph().find_variable("C").unwrap();
+
+        let classifier = Classifier::new(&graph);
+        let cycle = graph.mk_subspace(&[(b, true), (c, false)]);
+        let sink = graph.mk_subspace(&[(a, false), (b, false), (c, true)]);
+        classifier.add_component(cycle.clone(), &graph);
+        classifier.add_component(sink.clone(), &graph);
+
+        // Transient states are not part of any attractor.
+        let colors = graph.unit_colors();
+        let states = classifier.attractor_states(&graph, colors, None);
+        assert_eq!(cycle.union(&sink).as_bdd(), states.as_bdd());
+        let states = classifier.attractor_states(&graph, colors, Some(Behaviour::Oscillation));
+        assert_eq!(cycle.as_bdd(), states.as_bdd());
+
+        // Projection separates the states of the cycle, but only within its behaviour.
+        let oscillating = classifier
+            .project_attractors(&graph, colors, Some(Behaviour::Oscillation), vec![a])
+            .collect::<Vec<_>>();
+        assert_eq!(2, oscillating.len());
+        for item in &oscillating {
+            let [(var, value)] = item.valuation[..] else {
+                panic!("Expected a single projected variable.");
+            };
+            assert_eq!(a, var);
+            let expected = cycle.intersect(&graph.mk_subspace(&[(a, value)]));
+            assert_eq!(expected.as_bdd(), item.set.as_bdd());
+        }
+        let stable = classifier
+            .project_attractors(&graph, colors, Some(Behaviour::Stability), vec![a])
+            .collect::<Vec<_>>();
+        assert_eq!(1, stable.len());
+        assert_eq!(vec![(a, false)], stable[0].valuation);
+        assert_eq!(sink.as_bdd(), stable[0].set.as_bdd());
+
+        // Nothing is projected for behaviours without attractors or for no colours.
+        let disorder =
+            classifier.project_attractors(&graph, colors, Some(Behaviour::Disorder), vec![a]);
+        assert!(disorder.remaining().is_empty());
+        assert_eq!(0, disorder.count());
+        let empty = graph.mk_empty_colors();
+        let projection = classifier.project_attractors(&graph, &empty, None, vec![a, b]);
+        assert_eq!(0, projection.count());
+    }
+}
@@ -2,7 +2,7 @@ use crate::scc::ProgressTracker;
 use bigdecimal::BigDecimal;
 use biodivine_lib_param_bn::symbolic_async_graph::{GraphColoredVertices, SymbolicAsyncGraph};
 use num_bigint::BigUint;
-use num_traits::Zero;
+use num_traits::{ToPrimitive, Zero};
 use std::sync::Mutex;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
@@ -51,6 +51,22 @@ impl ProgressTracker {
         self.results.fetch_add(1, Ordering::SeqCst);
     }
 
+    pub fn get_result_count(&self) -> usize {
+        self.results.load(Ordering::SeqCst)
+    }
+
+    /// Fraction of the state space that is already processed (zero if not initialized).
+    pub fn get_progress_fraction(&self) -> f64 {
+        let total = self.total.lock().unwrap().clone();
+        if total.is_zero() {
+            return 0.0;
+        }
+        let remaining = BigDecimal::from_biguint(self.remaining.lock().unwrap().clone(), 0);
+        let total = BigDecimal::from_biguint(total, 0);
+        let remaining = (remaining / total).to_f64().unwrap_or(1.0);
+        (1.0 - remaining).clamp(0.0, 1.0)
+    }
+
     /// Output a string that represents the percentage of remaining state space.
     pub fn get_progress_string(&self) -> String {
         let remaining = BigDecimal::from_biguint(self.remaining.lock().unwrap().clone(), 0);
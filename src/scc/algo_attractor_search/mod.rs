@@ -0,0 +1,90 @@
+use crate::GraphTaskContext;
+use biodivine_algo_bdd_scc::attractor::{
+    AttractorConfig, InterleavedTransitionGuidedReduction, ItgrState, XieBeerelAttractors,
+};
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColoredVertices, SymbolicAsyncGraph};
+use cancel_this::Cancellable;
+use computation_process::{Computable, Generatable, Incomplete, Stateful};
+use std::collections::BTreeSet;
+
+/// Detect all attractors of the `graph` using ITGR reduction followed by the Xie-Beerel
+/// algorithm, calling `on_attractor` for each attractor as soon as it is found.
+///
+/// The progress is reported to the `task`, and the search stops once the `task` is
+/// cancelled. In that case, an error is returned, but the attractors found before the
+/// cancellation have already been reported.
+pub fn attractor_search<F>(
+    graph: &SymbolicAsyncGraph,
+    task: &GraphTaskContext,
+    mut on_attractor: F,
+) -> Cancellable<()>
+where
+    F: FnMut(GraphColoredVertices),
+{
+    cancel_this::on_trigger(task.is_cancelled.clone(), || {
+        // First, perform ITGR reduction.
+        let state = ItgrState::new(graph, graph.unit_colored_vertices());
+        let mut itgr = InterleavedTransitionGuidedReduction::configure(graph, state);
+        let universe = loop {
+            match itgr.try_compute() {
+                Ok(result) => break result,
+                Err(Incomplete::Cancelled(c)) => return Err(c),
+                Err(Incomplete::Suspended) => {
+                    task.update_remaining(itgr.state().remaining());
+                }
+                Err(e) => panic!("{}", e),
+            }
+        };
+
+        let active_variables = itgr.state().active_variables().collect::<BTreeSet<_>>();
+
+        // Then run Xie-Beerel to actually detect the components.
+        let mut config = AttractorConfig::new(graph.clone());
+        config.active_variables = active_variables;
+        let mut attractors = XieBeerelAttractors::configure(config, universe);
+
+        loop {
+            match attractors.try_next() {
+                None => break,
+                Some(Ok(component)) => {
+                    task.increment_result_count();
+                    on_attractor(component);
+                }
+                Some(Err(Incomplete::Cancelled(e))) => return Err(e),
+                Some(Err(Incomplete::Suspended)) => {
+                    task.update_remaining(attractors.state().remaining());
+                }
+                Some(Err(e)) => panic!("{}", e),
+            }
+        }
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GraphTaskContext;
+    use crate::scc::algo_attractor_search::attractor_search;
+    use biodivine_lib_param_bn::BooleanNetwork;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn attractor_search_results() {
+        // Two fixed points and one oscillating attractor of `C` (when `A` is active).
+        let network = BooleanNetwork::try_from(
+            "$A: !B\n$B: !A\n$C: A & !C\nB -| A\nA -| B\nA -> C\nC -| C\n",
+        )
+        .unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+
+        let task = GraphTaskContext::new();
+        let mut sizes = Vec::new();
+        let result = attractor_search(&graph, &task, |it| sizes.push(it.approx_cardinality()));
+        assert!(result.is_ok());
+        sizes.sort_by(f64::total_cmp);
+        assert_eq!(sizes, vec![1.0, 2.0]);
+        assert_eq!(task.get_result_count(), 2);
+    }
+}
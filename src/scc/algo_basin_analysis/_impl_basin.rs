@@ -0,0 +1,22 @@
+use crate::scc::AttractorSummary;
+use crate::scc::algo_basin_analysis::Basin;
+use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+use json::JsonValue;
+
+impl Basin {
+    /// Convert this basin to JSON. Both the weak and the strong basin are described using
+    /// an `AttractorSummary` (exact state count, sampled states and fixed variables).
+    ///
+    /// The basin should only contain a single colour, otherwise the sampled states mix
+    /// different parametrizations.
+    pub fn to_json(&self, graph: &SymbolicAsyncGraph, samples: usize) -> JsonValue {
+        let weak = AttractorSummary::compute(graph, &self.weak, samples);
+        let strong = AttractorSummary::compute(graph, &self.strong, samples);
+        object! {
+            "behaviour" => format!("{:?}", self.behaviour),
+            "attractor_state_count" => self.attractor.vertices().exact_cardinality().to_string(),
+            "weak" => weak.to_json(graph),
+            "strong" => strong.to_json(graph),
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use crate::GraphTaskContext;
+use crate::scc::Behaviour;
+use crate::scc::algo_reachability::backward_reach;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColoredVertices, SymbolicAsyncGraph};
+
+mod _impl_basin;
+
+/// Basins of attraction of one attractor.
+///
+/// The weak basin contains all states from which the attractor is reachable. The strong
+/// basin contains only the states from which no other attractor is reachable (i.e. the
+/// states that are guaranteed to end up in this attractor).
+#[derive(Clone, Debug)]
+pub struct Basin {
+    pub attractor: GraphColoredVertices,
+    pub behaviour: Behaviour,
+    pub weak: GraphColoredVertices,
+    pub strong: GraphColoredVertices,
+}
+
+/// Compute the weak and strong basins of all given attractors.
+///
+/// The attractors can be parametrized: the basins are then computed for each colour
+/// separately. However, the attractors should jointly cover all attractors of the colours
+/// they contain, otherwise the strong basins are over-approximated.
+///
+/// Returns `None` if the task is cancelled.
+pub fn compute_basins(
+    graph: &SymbolicAsyncGraph,
+    attractors: &[(GraphColoredVertices, Behaviour)],
+    task: &GraphTaskContext,
+) -> Option<Vec<Basin>> {
+    let mut colors = graph.mk_empty_colors();
+    for (attractor, _) in attractors {
+        colors = colors.union(&attractor.colors());
+    }
+    let universe = graph.unit_colored_vertices().intersect_colors(&colors);
+
+    let mut weak_basins = Vec::new();
+    for (attractor, _) in attractors {
+        weak_basins.push(backward_reach(graph, attractor, &universe, task)?);
+    }
+
+    let basins = attractors
+        .iter()
+        .enumerate()
+        .map(|(i, (attractor, behaviour))| {
+            let mut strong = weak_basins[i].clone();
+            for (j, other) in weak_basins.iter().enumerate() {
+                if i != j {
+                    strong = strong.minus(other);
+                }
+            }
+            Basin {
+                attractor: attractor.clone(),
+                behaviour: *behaviour,
+                weak: weak_basins[i].clone(),
+                strong,
+            }
+        })
+        .collect();
+    Some(basins)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GraphTaskContext;
+    use crate::scc::Behaviour;
+    use crate::scc::algo_basin_analysis::compute_basins;
+    use biodivine_lib_param_bn::BooleanNetwork;
+    use biodivine_lib_param_bn::biodivine_std::traits::Set;
+    use biodivine_lib_param_bn::symbolic_async_graph::{GraphColoredVertices, SymbolicAsyncGraph};
+    use std::convert::TryFrom;
+
+    // While `C` is off, `A` oscillates and eventually sets the latch `B`, giving the cyclic
+    // attractor `B & !C`. Before the latch is set, `C` can switch on instead, which stops `A`
+    // and leads to the fixed point `!A & !B & C`.
+    const MODEL: &str = "A -| A\nC -| A\nA -> B\nB -> B\nC -| B\nA -| C\nB -| C\nC -> C\n\
+        $A: !A & !C\n$B: B | (A & !C)\n$C: !B & (C | !A)\n";
+
+    fn attractors(
+        network: &BooleanNetwork,
+        graph: &SymbolicAsyncGraph,
+    ) -> [(GraphColoredVertices, Behaviour); 2] {
+        let var = |name: &str| network.as_graph().find_variable(name).unwrap();
+        [
+            (
+                graph.mk_subspace(&[(var("B"), true), (var("C"), false)]),
+                Behaviour::Oscillation,
+            ),
+            (
+                graph.mk_subspace(&[(var("A"), false), (var("B"), false), (var("C"), true)]),
+                Behaviour::Stability,
+            ),
+        ]
+    }
+
+    #[test]
+    fn oscillating_attractor_basins() {
+        let network = BooleanNetwork::try_from(MODEL).unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let b = network.as_graph().find_variable("B").unwrap();
+        let c = network.as_graph().find_variable("C").unwrap();
+        let attractors = attractors(&network, &graph);
+
+        let basins = compute_basins(&graph, &attractors, &GraphTaskContext::new()).unwrap();
+        let (oscillation, stability) = (&basins[0], &basins[1]);
+        assert_eq!(Behaviour::Oscillation, oscillation.behaviour);
+        assert_eq!(6.0, oscillation.weak.approx_cardinality());
+        assert_eq!(4.0, stability.weak.approx_cardinality());
+
+        // Once the latch is set, only the cycle is reachable, even though `C` can be on.
+        let latched = graph.mk_subspace(&[(b, true)]);
+        assert_eq!(latched.as_bdd(), oscillation.strong.as_bdd());
+        assert!(oscillation.attractor.is_subset(&oscillation.strong));
+        assert_ne!(oscillation.attractor.as_bdd(), oscillation.strong.as_bdd());
+        let stopped = graph.mk_subspace(&[(b, false), (c, true)]);
+        assert_eq!(stopped.as_bdd(), stability.strong.as_bdd());
+
+        // States where the race between `A` and `C` is still open belong to both weak basins.
+        let open = graph.mk_subspace(&[(b, false), (c, false)]);
+        let shared = oscillation.weak.intersect(&stability.weak);
+        assert_eq!(open.as_bdd(), shared.as_bdd());
+        assert!(oscillation.strong.intersect(&stability.weak).is_empty());
+    }
+
+    #[test]
+    fn cancelled_basins() {
+        let network = BooleanNetwork::try_from(MODEL).unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let task = GraphTaskContext::new();
+        task.cancel();
+        assert!(compute_basins(&graph, &attractors(&network, &graph), &task).is_none());
+    }
+}
@@ -0,0 +1,84 @@
+use crate::scc::algo_projection::Projection;
+use biodivine_lib_param_bn::VariableId;
+use biodivine_lib_param_bn::fixed_points::FixedPoints;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColoredVertices, SymbolicAsyncGraph};
+
+/// Compute all fixed points (sink states) within the `restriction` set, together with
+/// the colours in which they are fixed points.
+///
+/// Unlike the attractor search, this is a direct symbolic computation which does not need
+/// to explore the state space.
+pub fn fixed_points(
+    graph: &SymbolicAsyncGraph,
+    restriction: &GraphColoredVertices,
+) -> GraphColoredVertices {
+    FixedPoints::symbolic(graph, restriction)
+}
+
+/// Enumerate fixed points within `restriction`, projected onto the given `variables`
+/// (see `Projection`).
+pub fn projected_fixed_points<'a>(
+    graph: &'a SymbolicAsyncGraph,
+    restriction: &GraphColoredVertices,
+    variables: Vec<VariableId>,
+) -> Projection<'a> {
+    Projection::new(graph, fixed_points(graph, restriction), variables)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scc::algo_fixed_points::{fixed_points, projected_fixed_points};
+    use biodivine_lib_param_bn::BooleanNetwork;
+    use biodivine_lib_param_bn::biodivine_std::traits::Set;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use std::convert::TryFrom;
+
+    // `A` and `B` are constant, while `C` oscillates if both are on. Hence every state
+    // outside of `A & B` is a fixed point.
+    const MODEL: &str = "A -> A\nB -> B\nA -? C\nB -? C\nC -? C\n$A: A\n$B: B\n$C: C ^ (A & B)\n";
+
+    #[test]
+    fn fixed_points_skip_oscillation() {
+        let network = BooleanNetwork::try_from(MODEL).unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let a = network.as_graph().find_variable("A").unwrap();
+        let b = network.as_graph().find_variable("B").unwrap();
+
+        let oscillation = graph.mk_subspace(&[(a, true), (b, true)]);
+        let expected = graph.unit_colored_vertices().minus(&oscillation);
+        let fixed = fixed_points(&graph, graph.unit_colored_vertices());
+        assert_eq!(expected.as_bdd(), fixed.as_bdd());
+        assert!(fixed_points(&graph, &oscillation).is_empty());
+    }
+
+    #[test]
+    fn projection_merges_fixed_points() {
+        let network = BooleanNetwork::try_from(MODEL).unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let a = network.as_graph().find_variable("A").unwrap();
+        let unit = graph.unit_colored_vertices();
+
+        // Fixed points with `A` on require `B` off, but `C` can have any value.
+        let projection = projected_fixed_points(&graph, unit, vec![a]);
+        assert_eq!(
+            fixed_points(&graph, unit).as_bdd(),
+            projection.remaining().as_bdd()
+        );
+        let mut items = projection.collect::<Vec<_>>();
+        items.sort_by_key(|item| item.valuation[0].1);
+        assert_eq!(2, items.len());
+        assert_eq!(vec![(a, false)], items[0].valuation);
+        assert_eq!(vec![(a, true)], items[1].valuation);
+        assert_eq!(4.0, items[0].set.vertices().approx_cardinality());
+        assert_eq!(2.0, items[1].set.vertices().approx_cardinality());
+        let json = items[1].to_json(&graph);
+        assert_eq!(json["valuation"]["A"], true);
+        assert_eq!(json["state_count"], "2");
+
+        // An empty projection yields all fixed points as a single item.
+        let items = projected_fixed_points(&graph, unit, Vec::new()).collect::<Vec<_>>();
+        assert_eq!(1, items.len());
+        assert!(items[0].valuation.is_empty());
+        assert_eq!(fixed_points(&graph, unit).as_bdd(), items[0].set.as_bdd());
+    }
+}
@@ -0,0 +1,22 @@
+use crate::scc::algo_projection::ProjectedValuation;
+use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+use json::JsonValue;
+
+impl ProjectedValuation {
+    /// Convert this item to JSON: the projected valuation (variable names mapped to values),
+    /// the number of colours and the exact number of (full) states with this valuation (as
+    /// a decimal string, like in other responses).
+    pub fn to_json(&self, graph: &SymbolicAsyncGraph) -> JsonValue {
+        let mut valuation = JsonValue::new_object();
+        for (var, value) in &self.valuation {
+            valuation
+                .insert(&graph.get_variable_name(*var), *value)
+                .unwrap();
+        }
+        object! {
+            "valuation" => valuation,
+            "cardinality" => self.set.colors().approx_cardinality(),
+            "state_count" => self.set.vertices().exact_cardinality().to_string(),
+        }
+    }
+}
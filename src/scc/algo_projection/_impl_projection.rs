@@ -0,0 +1,59 @@
+use crate::scc::algo_projection::{ProjectedValuation, Projection};
+use biodivine_lib_param_bn::VariableId;
+use biodivine_lib_param_bn::biodivine_std::bitvector::BitVector;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColoredVertices, SymbolicAsyncGraph};
+
+impl<'a> Projection<'a> {
+    /// Create a projection of `set` onto the given `variables`.
+    ///
+    /// If `variables` is empty, the whole set is returned as a single item.
+    pub fn new(
+        graph: &'a SymbolicAsyncGraph,
+        set: GraphColoredVertices,
+        variables: Vec<VariableId>,
+    ) -> Projection<'a> {
+        Projection {
+            graph,
+            variables,
+            remaining: set,
+        }
+    }
+
+    pub fn variables(&self) -> &[VariableId] {
+        &self.variables
+    }
+
+    /// The part of the projected set which was not enumerated yet (before the iteration
+    /// starts, this is the whole set).
+    pub fn remaining(&self) -> &GraphColoredVertices {
+        &self.remaining
+    }
+}
+
+impl Iterator for Projection<'_> {
+    type Item = ProjectedValuation;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        let state = self
+            .remaining
+            .pick_vertex()
+            .vertices()
+            .materialize()
+            .iter()
+            .next()?;
+        let valuation = self
+            .variables
+            .iter()
+            .map(|var| (*var, state.get(var.to_index())))
+            .collect::<Vec<_>>();
+        let set = self
+            .remaining
+            .intersect(&self.graph.mk_subspace(&valuation));
+        self.remaining = self.remaining.minus(&set);
+        Some(ProjectedValuation { valuation, set })
+    }
+}
@@ -0,0 +1,25 @@
+use biodivine_lib_param_bn::VariableId;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColoredVertices, SymbolicAsyncGraph};
+
+mod _impl_projected_valuation;
+mod _impl_projection;
+
+/// A streaming enumeration of a coloured set of states projected onto a subset of variables.
+///
+/// Each item is one distinct valuation of the selected variables, together with all the
+/// states and colours of the original set that have this valuation. The enumeration is
+/// output-sensitive, i.e. taking only the first `k` items never explores the whole set.
+pub struct Projection<'a> {
+    graph: &'a SymbolicAsyncGraph,
+    variables: Vec<VariableId>,
+    remaining: GraphColoredVertices,
+}
+
+/// One item of a `Projection`.
+#[derive(Clone, Debug)]
+pub struct ProjectedValuation {
+    /// Values of the projection variables (in the order of `Projection` variables).
+    pub valuation: Vec<(VariableId, bool)>,
+    /// All states and colours of the original set that have this valuation.
+    pub set: GraphColoredVertices,
+}
@@ -0,0 +1,149 @@
+use crate::GraphTaskContext;
+use biodivine_lib_param_bn::VariableId;
+use biodivine_lib_param_bn::biodivine_std::bitvector::ArrayBitVector;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColoredVertices, SymbolicAsyncGraph};
+
+/// Compute the set of states (and colours) from which `initial` is reachable, staying
+/// within the `universe` set.
+///
+/// The computation uses saturation (always applies the "last" variable that can add new
+/// states). Returns `None` if the task is cancelled before the fixed point is reached.
+pub fn backward_reach(
+    graph: &SymbolicAsyncGraph,
+    initial: &GraphColoredVertices,
+    universe: &GraphColoredVertices,
+    task: &GraphTaskContext,
+) -> Option<GraphColoredVertices> {
+    reach(graph, initial, universe, task, |var, set| {
+        graph.var_pre(var, set)
+    })
+}
+
+/// Compute the set of states (and colours) reachable from `initial`, staying within
+/// the `universe` set.
+///
+/// See `backward_reach` for details.
+pub fn forward_reach(
+    graph: &SymbolicAsyncGraph,
+    initial: &GraphColoredVertices,
+    universe: &GraphColoredVertices,
+    task: &GraphTaskContext,
+) -> Option<GraphColoredVertices> {
+    reach(graph, initial, universe, task, |var, set| {
+        graph.var_post(var, set)
+    })
+}
+
+/// **(internal)** Generic saturation-based reachability using the given `step` function.
+fn reach<F>(
+    graph: &SymbolicAsyncGraph,
+    initial: &GraphColoredVertices,
+    universe: &GraphColoredVertices,
+    task: &GraphTaskContext,
+    step: F,
+) -> Option<GraphColoredVertices>
+where
+    F: Fn(VariableId, &GraphColoredVertices) -> GraphColoredVertices,
+{
+    let mut result = initial.intersect(universe);
+    'reach: loop {
+        if task.is_cancelled() {
+            return None;
+        }
+        for var in graph.variables().rev() {
+            let successors = step(var, &result).intersect(universe).minus(&result);
+            if !successors.is_empty() {
+                result = result.union(&successors);
+                continue 'reach;
+            }
+        }
+        return Some(result);
+    }
+}
+
+/// Find a shortest path from some state of `source` to some state of `target`.
+///
+/// Both sets should contain a single colour, otherwise the path can mix transitions of
+/// different parametrizations. Each path step contains the state and the variable updated to
+/// reach it (`None` for the first state). Returns `None` if no path exists or if the task
+/// is cancelled (the two cases can be distinguished using `task.is_cancelled()`).
+pub fn find_path(
+    graph: &SymbolicAsyncGraph,
+    source: &GraphColoredVertices,
+    target: &GraphColoredVertices,
+    task: &GraphTaskContext,
+) -> Option<Vec<(ArrayBitVector, Option<VariableId>)>> {
+    // Layered breadth-first search, each layer contains only states not seen before.
+    let mut layers = vec![source.clone()];
+    let mut visited = source.clone();
+    while layers.last().unwrap().intersect(target).is_empty() {
+        if task.is_cancelled() {
+            return None;
+        }
+        let successors = graph.post(layers.last().unwrap()).minus(&visited);
+        if successors.is_empty() {
+            return None;
+        }
+        visited = visited.union(&successors);
+        layers.push(successors);
+    }
+
+    // Walk back through the layers, always picking a predecessor of the current state.
+    let mut current = layers.pop().unwrap().intersect(target).pick_vertex();
+    let mut path = Vec::new();
+    while let Some(layer) = layers.pop() {
+        let (variable, predecessor) = graph
+            .variables()
+            .map(|var| (var, graph.var_pre(var, &current).intersect(&layer)))
+            .find(|(_, predecessor)| !predecessor.is_empty())
+            .unwrap();
+        path.push((first_state(&current), Some(variable)));
+        current = predecessor.pick_vertex();
+    }
+    path.push((first_state(&current), None));
+    path.reverse();
+    Some(path)
+}
+
+/// **(internal)** Extract the (first) state of a non-empty set.
+fn first_state(set: &GraphColoredVertices) -> ArrayBitVector {
+    set.vertices().materialize().iter().next().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GraphTaskContext;
+    use crate::scc::algo_reachability::{backward_reach, find_path, forward_reach};
+    use biodivine_lib_param_bn::BooleanNetwork;
+    use biodivine_lib_param_bn::biodivine_std::bitvector::BitVector;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn reachability_and_paths() {
+        // `A` is constant and `B` follows `A`, hence `B` can only change towards `A`.
+        let network = BooleanNetwork::try_from("A -> B\nA -> A\n$A: A\n$B: A\n").unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let a = network.as_graph().find_variable("A").unwrap();
+        let b = network.as_graph().find_variable("B").unwrap();
+        let task = GraphTaskContext::new();
+        let unit = graph.mk_unit_colored_vertices();
+        let state = |va, vb| graph.mk_subspace(&[(a, va), (b, vb)]);
+
+        let forward = forward_reach(&graph, &state(true, false), &unit, &task).unwrap();
+        assert_eq!(forward.approx_cardinality(), 2.0);
+        let backward = backward_reach(&graph, &state(false, true), &unit, &task).unwrap();
+        assert_eq!(backward.approx_cardinality(), 1.0);
+
+        let path = find_path(&graph, &state(true, false), &state(true, true), &task).unwrap();
+        assert_eq!(path.len(), 2);
+        assert_eq!(path[0].1, None);
+        assert_eq!(path[1].1, Some(b));
+        assert!(path[1].0.get(b.to_index()));
+        assert!(find_path(&graph, &state(true, true), &state(true, false), &task).is_none());
+
+        task.cancel();
+        assert!(forward_reach(&graph, &state(true, false), &unit, &task).is_none());
+    }
+}
@@ -0,0 +1,113 @@
+use crate::scc::algo_stability_analysis::{JointStability, StabilityVector, VariableStability};
+use crate::util::functional::Functional;
+use biodivine_lib_param_bn::VariableId;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{
+    GraphColoredVertices, GraphColors, SymbolicAsyncGraph,
+};
+use json::JsonValue;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+impl JointStability {
+    /// Compute joint stability data for the given variables and all available attractors.
+    pub fn for_attractors(
+        graph: &SymbolicAsyncGraph,
+        attractors: &[GraphColoredVertices],
+        variables: &[VariableId],
+    ) -> JointStability {
+        let all_colors = attractors
+            .iter()
+            .fold(graph.mk_empty_colors(), |a, b| a.union(&b.colors()));
+        let mut data = HashMap::from([(Vec::new(), all_colors)]);
+        for variable in variables {
+            let stability = VariableStability::for_attractors(graph, attractors, *variable);
+            let mut updated_data = HashMap::new();
+            for (key, colors) in data {
+                for (vector, vector_colors) in stability.to_vec() {
+                    let colors = colors.intersect(&vector_colors);
+                    if !colors.is_empty() {
+                        let key = key.clone().apply(|it| it.push(vector));
+                        updated_data.insert(key, colors);
+                    }
+                }
+            }
+            data = updated_data;
+        }
+        JointStability {
+            variables: variables.to_vec(),
+            data,
+        }
+    }
+
+    pub fn variables(&self) -> &[VariableId] {
+        &self.variables
+    }
+
+    /// Colours where the variables have the given stability vectors (if any).
+    pub fn get(&self, vectors: &[StabilityVector]) -> Option<&GraphColors> {
+        self.data.get(vectors)
+    }
+
+    /// Convert this stability data to a vector of pairs (sorted by the stability vectors).
+    pub fn to_vec(&self) -> Vec<(Vec<StabilityVector>, GraphColors)> {
+        self.data
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect::<Vec<_>>()
+            .apply(|it| it.sort_by(|(a, _), (b, _)| a.cmp(b)))
+    }
+
+    /// Read a list of stability vectors separated by `;`, e.g. `[true];[false,unstable]`.
+    pub fn parse_vectors(value: &str) -> Result<Vec<StabilityVector>, String> {
+        value.split(';').map(StabilityVector::try_from).collect()
+    }
+
+    pub fn to_json(&self) -> JsonValue {
+        JsonValue::new_array().apply(|array| {
+            for (vectors, colors) in self.to_vec() {
+                let key = vectors
+                    .iter()
+                    .map(|it| it.to_string())
+                    .collect::<Vec<_>>()
+                    .join(";");
+                array
+                    .push(object! {
+                        "key": key,
+                        "vectors": vectors.iter().map(|it| it.export_json()).collect::<Vec<_>>(),
+                        "colors": colors.approx_cardinality(),
+                    })
+                    .unwrap();
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scc::algo_fixed_points::fixed_points;
+    use crate::scc::algo_stability_analysis::{JointStability, StabilityVector};
+    use biodivine_lib_param_bn::BooleanNetwork;
+    use biodivine_lib_param_bn::biodivine_std::traits::Set;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn joint_stability_combines_variables() {
+        // `A` is always true, `B` is given by the parameter `p`.
+        let network = BooleanNetwork::try_from("$A: true\n$B: p\n").unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let a = network.as_graph().find_variable("A").unwrap();
+        let b = network.as_graph().find_variable("B").unwrap();
+        let fixed = fixed_points(&graph, graph.unit_colored_vertices());
+        let b_true = fixed.intersect(&graph.mk_subspace(&[(b, true)])).colors();
+        let b_false = graph.mk_unit_colors().minus(&b_true);
+
+        let joint = JointStability::for_attractors(&graph, &[fixed], &[a, b]);
+        let stable_true = StabilityVector::try_from("[true]").unwrap();
+        let stable_false = StabilityVector::try_from("[false]").unwrap();
+        assert_eq!(joint.get(&[stable_true, stable_true]), Some(&b_true));
+        assert_eq!(joint.get(&[stable_true, stable_false]), Some(&b_false));
+        assert_eq!(joint.to_vec().len(), 2);
+    }
+}
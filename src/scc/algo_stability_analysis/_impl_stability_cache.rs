@@ -0,0 +1,167 @@
+use crate::scc::Behaviour;
+use crate::scc::algo_stability_analysis::{StabilityCache, VariableStability};
+use biodivine_lib_param_bn::VariableId;
+use biodivine_lib_param_bn::symbolic_async_graph::{
+    GraphColoredVertices, GraphColors, SymbolicAsyncGraph,
+};
+use moka::sync::Cache;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The default memory budget of a `StabilityCache` (in bytes).
+const MAX_CACHED_STABILITY_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Approximate size of one BDD node in memory (a variable and two pointers).
+const BDD_NODE_BYTES: usize = 12;
+
+impl Default for StabilityCache {
+    fn default() -> Self {
+        StabilityCache::new(MAX_CACHED_STABILITY_BYTES)
+    }
+}
+
+impl StabilityCache {
+    /// Create a cache whose keys and values occupy at most (approximately) `max_bytes`.
+    pub fn new(max_bytes: u64) -> StabilityCache {
+        StabilityCache {
+            data: Cache::builder()
+                .max_capacity(max_bytes)
+                .weigher(
+                    |key: &(GraphColors, Option<Behaviour>, VariableId),
+                     stability: &VariableStability| {
+                        entry_weight(&key.0, stability)
+                    },
+                )
+                .build(),
+            generation: AtomicUsize::new(0),
+        }
+    }
+
+    /// Get the cached stability of `variable` for the given colours and behaviour, if present.
+    pub fn get(
+        &self,
+        colors: &GraphColors,
+        behaviour: Option<Behaviour>,
+        variable: VariableId,
+    ) -> Option<VariableStability> {
+        self.data.get(&(colors.clone(), behaviour, variable))
+    }
+
+    /// Get the cached stability of `variable`, or compute it using the given attractors
+    /// (which must be the attractors restricted to `colors` and `behaviour`).
+    pub fn get_or_compute(
+        &self,
+        graph: &SymbolicAsyncGraph,
+        colors: &GraphColors,
+        behaviour: Option<Behaviour>,
+        variable: VariableId,
+        attractors: &[GraphColoredVertices],
+    ) -> VariableStability {
+        if let Some(stability) = self.get(colors, behaviour, variable) {
+            return stability;
+        }
+        // The cache is not locked during the computation, so the same value can be computed
+        // concurrently, but the result is the same anyway.
+        let generation = self.generation();
+        let stability = VariableStability::for_attractors(graph, attractors, variable);
+        self.insert(generation, colors, behaviour, variable, stability.clone());
+        stability
+    }
+
+    /// Save a computed value, unless the cache was cleared since `generation`
+    /// (i.e. the value was computed for a computation which no longer exists).
+    pub fn insert(
+        &self,
+        generation: usize,
+        colors: &GraphColors,
+        behaviour: Option<Behaviour>,
+        variable: VariableId,
+        stability: VariableStability,
+    ) {
+        if self.generation() != generation {
+            return;
+        }
+        let key = (colors.clone(), behaviour, variable);
+        self.data.insert(key.clone(), stability);
+        // If the cache was cleared concurrently after the first check, the value may have
+        // been inserted after `invalidate_all`, hence it has to be removed again.
+        if self.generation() != generation {
+            self.data.invalidate(&key);
+        }
+    }
+
+    /// Remove all cached values. This must be called whenever the underlying graph changes.
+    pub fn clear(&self) {
+        // The generation changes first, so that no stale value survives `invalidate_all`.
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.data.invalidate_all();
+    }
+
+    /// A counter which is incremented every time the cache is cleared.
+    pub fn generation(&self) -> usize {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.run_pending_tasks();
+        usize::try_from(self.data.entry_count()).unwrap_or(usize::MAX)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Approximate memory footprint of one cache entry, based on the size of its BDDs.
+fn entry_weight(colors: &GraphColors, stability: &VariableStability) -> u32 {
+    let nodes = stability
+        .0
+        .iter()
+        .flatten()
+        .map(|it| it.as_bdd().size())
+        .sum::<usize>()
+        + colors.as_bdd().size();
+    u32::try_from(nodes.saturating_mul(BDD_NODE_BYTES)).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scc::algo_fixed_points::fixed_points;
+    use crate::scc::algo_stability_analysis::_impl_stability_cache::entry_weight;
+    use crate::scc::algo_stability_analysis::StabilityCache;
+    use biodivine_lib_param_bn::BooleanNetwork;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn stability_cache_generations() {
+        let network = BooleanNetwork::try_from("$A: p\n$B: true\n").unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let a = network.as_graph().find_variable("A").unwrap();
+        let b = network.as_graph().find_variable("B").unwrap();
+        let attractors = [fixed_points(&graph, graph.unit_colored_vertices())];
+        let colors = graph.mk_unit_colors();
+
+        let cache = StabilityCache::default();
+        let stability = cache.get_or_compute(&graph, &colors, None, a, &attractors);
+        assert_eq!(cache.get(&colors, None, a), Some(stability.clone()));
+        assert_eq!(cache.len(), 1);
+
+        // Values computed before the cache is cleared are not saved.
+        let generation = cache.generation();
+        cache.clear();
+        assert!(cache.is_empty());
+        cache.insert(generation, &colors, None, a, stability.clone());
+        assert_eq!(cache.get(&colors, None, a), None);
+        cache.insert(cache.generation(), &colors, None, a, stability);
+        assert!(cache.get(&colors, None, a).is_some());
+
+        // The memory used by the values is bounded: the budget below fits only one entry.
+        let stability = cache.get_or_compute(&graph, &colors, None, b, &attractors);
+        let weight = u64::from(entry_weight(&colors, &stability));
+        assert!(weight > 0);
+        let cache = StabilityCache::new(weight + weight / 2);
+        cache.get_or_compute(&graph, &colors, None, a, &attractors);
+        cache.get_or_compute(&graph, &colors, None, b, &attractors);
+        assert!(cache.len() <= 1);
+    }
+}
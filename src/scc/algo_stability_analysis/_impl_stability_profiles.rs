@@ -0,0 +1,210 @@
+use crate::scc::algo_stability_analysis::{AttractorStabilityData, Stability, StabilityProfiles};
+use crate::util::functional::Functional;
+use biodivine_lib_param_bn::VariableId;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{
+    GraphColoredVertices, GraphColors, SymbolicAsyncGraph,
+};
+use json::JsonValue;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+impl StabilityProfiles {
+    /// Compute attractor-resolved stability profiles of the given variables.
+    ///
+    /// If `ordered` is false, the profiles of each colour are sorted, i.e. two colours
+    /// with the same multiset of attractor profiles end up in the same group. Otherwise,
+    /// the i-th profile belongs to the i-th of the given `attractors`, and an empty profile
+    /// marks an attractor that does not exist in the colours.
+    pub fn for_attractors(
+        graph: &SymbolicAsyncGraph,
+        attractors: &[GraphColoredVertices],
+        variables: &[VariableId],
+        ordered: bool,
+    ) -> StabilityProfiles {
+        let all_colors = attractors
+            .iter()
+            .fold(graph.mk_empty_colors(), |a, b| a.union(&b.colors()));
+        let mut data: HashMap<Vec<Vec<Stability>>, GraphColors> =
+            HashMap::from([(Vec::new(), all_colors)]);
+        for attractor in attractors {
+            let profiles = Self::attractor_profiles(graph, attractor, variables);
+            let attractor_colors = attractor.colors();
+            let mut updated_data = HashMap::new();
+            for (key, colors) in data {
+                let not_present = colors.minus(&attractor_colors);
+                if !not_present.is_empty() {
+                    // Keep the positions of the remaining attractors in ordered mode.
+                    let key = if ordered {
+                        key.clone().apply(|it| it.push(Vec::new()))
+                    } else {
+                        key.clone()
+                    };
+                    push(&mut updated_data, key, not_present);
+                }
+                for (profile, profile_colors) in &profiles {
+                    let colors = colors.intersect(profile_colors);
+                    if !colors.is_empty() {
+                        let key = key.clone().apply(|it| {
+                            it.push(profile.clone());
+                            if !ordered {
+                                it.sort();
+                            }
+                        });
+                        push(&mut updated_data, key, colors);
+                    }
+                }
+            }
+            data = updated_data;
+        }
+        StabilityProfiles {
+            variables: variables.to_vec(),
+            ordered,
+            data,
+        }
+    }
+
+    /// **(internal)** Split the colours of one attractor based on the stability of all
+    /// the given variables (i.e. the stability profile of the attractor).
+    fn attractor_profiles(
+        graph: &SymbolicAsyncGraph,
+        attractor: &GraphColoredVertices,
+        variables: &[VariableId],
+    ) -> Vec<(Vec<Stability>, GraphColors)> {
+        let mut profiles = vec![(Vec::new(), attractor.colors())];
+        for variable in variables {
+            let stability = AttractorStabilityData::for_attractor(graph, attractor, *variable);
+            let mut updated = Vec::new();
+            for (profile, colors) in profiles {
+                for value in [Stability::True, Stability::False, Stability::Unstable] {
+                    let colors = colors.intersect(&stability[value]);
+                    if !colors.is_empty() {
+                        updated.push((profile.clone().apply(|it| it.push(value)), colors));
+                    }
+                }
+            }
+            profiles = updated;
+        }
+        profiles
+    }
+
+    pub fn variables(&self) -> &[VariableId] {
+        &self.variables
+    }
+
+    pub fn is_ordered(&self) -> bool {
+        self.ordered
+    }
+
+    /// Colours with the given list of attractor profiles (if any).
+    pub fn get(&self, profiles: &[Vec<Stability>]) -> Option<&GraphColors> {
+        if self.ordered {
+            self.data.get(profiles)
+        } else {
+            self.data.get(&profiles.to_vec().apply(|it| it.sort()))
+        }
+    }
+
+    /// Convert a list of attractor profiles to a string, e.g. `(true,false);(false,false)`.
+    /// An absent attractor (in ordered profiles) is written as `()`.
+    pub fn key_to_string(profiles: &[Vec<Stability>]) -> String {
+        profiles
+            .iter()
+            .map(|profile| {
+                let values = profile.iter().map(|it| it.to_string()).collect::<Vec<_>>();
+                format!("({})", values.join(","))
+            })
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    /// Read a list of attractor profiles written using `StabilityProfiles::key_to_string`.
+    pub fn parse_key(value: &str) -> Result<Vec<Vec<Stability>>, String> {
+        value
+            .split(';')
+            .map(|profile| {
+                let Some(profile) = profile
+                    .strip_prefix('(')
+                    .and_then(|it| it.strip_suffix(')'))
+                else {
+                    return Err(format!("Invalid stability profile: `{}`.", profile));
+                };
+                if profile.is_empty() {
+                    return Ok(Vec::new());
+                }
+                profile.split(',').map(Stability::try_from).collect()
+            })
+            .collect()
+    }
+
+    pub fn to_json(&self) -> JsonValue {
+        let mut entries = self.data.iter().collect::<Vec<_>>();
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        JsonValue::new_array().apply(|array| {
+            for (profiles, colors) in entries {
+                let attractors = profiles
+                    .iter()
+                    .map(|profile| profile.iter().map(|it| it.to_string()).collect::<Vec<_>>())
+                    .collect::<Vec<_>>();
+                array
+                    .push(object! {
+                        "key": Self::key_to_string(profiles),
+                        "attractors": attractors,
+                        "colors": colors.approx_cardinality(),
+                    })
+                    .unwrap();
+            }
+        })
+    }
+}
+
+/// **(internal)** Insert the colours into the map, or union them with the current value.
+fn push(
+    data: &mut HashMap<Vec<Vec<Stability>>, GraphColors>,
+    key: Vec<Vec<Stability>>,
+    colors: GraphColors,
+) {
+    if let Some(current) = data.get_mut(&key) {
+        *current = current.union(&colors);
+    } else {
+        data.insert(key, colors);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scc::algo_fixed_points::fixed_points;
+    use crate::scc::algo_stability_analysis::{Stability, StabilityProfiles};
+    use biodivine_lib_param_bn::BooleanNetwork;
+    use biodivine_lib_param_bn::biodivine_std::traits::Set;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+
+    #[test]
+    fn ordered_profiles_keep_positions() {
+        // Each colour has exactly one fixed point (depending on `p`), both with `A` true.
+        let network = BooleanNetwork::try_from("$A: true\n$B: p\n").unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let a = network.as_graph().find_variable("A").unwrap();
+        let b = network.as_graph().find_variable("B").unwrap();
+        let fixed = fixed_points(&graph, graph.unit_colored_vertices());
+        let attractors =
+            [true, false].map(|value| fixed.intersect(&graph.mk_subspace(&[(b, value)])));
+        let present = vec![Stability::True];
+
+        let ordered = StabilityProfiles::for_attractors(&graph, &attractors, &[a], true);
+        assert!(ordered.get(&[present.clone(), Vec::new()]).is_some());
+        assert!(ordered.get(&[Vec::new(), present.clone()]).is_some());
+        assert!(ordered.get(std::slice::from_ref(&present)).is_none());
+
+        let unordered = StabilityProfiles::for_attractors(&graph, &attractors, &[a], false);
+        let all_colors = graph.mk_unit_colors();
+        assert_eq!(
+            unordered.get(std::slice::from_ref(&present)),
+            Some(&all_colors)
+        );
+
+        let key = StabilityProfiles::key_to_string(&[present, Vec::new()]);
+        assert_eq!(key, "(true);()");
+        assert_eq!(StabilityProfiles::parse_key(&key).unwrap()[1], Vec::new());
+    }
+}
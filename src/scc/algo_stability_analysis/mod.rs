@@ -1,11 +1,17 @@
+use crate::scc::Behaviour;
 use biodivine_lib_param_bn::VariableId;
 use biodivine_lib_param_bn::symbolic_async_graph::{
     GraphColoredVertices, GraphColors, SymbolicAsyncGraph,
 };
+use moka::sync::Cache;
 use std::collections::HashMap;
+use std::sync::atomic::AtomicUsize;
 
 mod _impl_attractor_stability_data;
+mod _impl_joint_stability;
 mod _impl_stability;
+mod _impl_stability_cache;
+mod _impl_stability_profiles;
 mod _impl_stability_vector;
 mod _impl_variable_stability;
 
@@ -49,6 +55,47 @@ pub struct StabilityVector {
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
 pub struct VariableStability([Option<GraphColors>; 8]);
 
+/// Joint stability of multiple variables in multiple attractors: a mapping from vectors of
+/// `StabilityVector` values (one for each variable, in the order of `variables`) to
+/// `GraphColors`.
+///
+/// The data is the combination (intersection) of the individual `VariableStability` results,
+/// grouped by all variables at once. This allows to answer questions like "in which
+/// parametrizations are both `A` and `B` stable-true in all attractors" directly. To tell which
+/// attractor has which values, use `StabilityProfiles` instead.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct JointStability {
+    variables: Vec<VariableId>,
+    data: HashMap<Vec<StabilityVector>, GraphColors>,
+}
+
+/// Attractor-resolved stability of multiple variables: colours are grouped by the list of
+/// stability profiles of their attractors, where a profile is the `Stability` of each
+/// variable (in the order of `variables`) in one attractor.
+///
+/// Unlike `StabilityVector`, this keeps track of which attractor has which value, e.g. for
+/// a bistable switch, it distinguishes `(true,false);(false,true)` from
+/// `(true,true);(false,false)`. If the profiles are not `ordered`, each list is sorted,
+/// otherwise it follows the order of the analysed attractors.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct StabilityProfiles {
+    variables: Vec<VariableId>,
+    ordered: bool,
+    data: HashMap<Vec<Vec<Stability>>, GraphColors>,
+}
+
+/// A thread-safe cache of `VariableStability` results, keyed by the analysed colours (e.g.
+/// colours of a tree node), the behaviour filter and the variable.
+///
+/// Since the key contains the colours themselves, the values remain valid when the decision
+/// tree changes. However, the cache must be cleared once the underlying graph changes.
+/// The memory used by the cached colour sets is bounded (approximated from the sizes of
+/// their BDDs), the least useful values are evicted first.
+pub struct StabilityCache {
+    data: Cache<(GraphColors, Option<Behaviour>, VariableId), VariableStability>,
+    generation: AtomicUsize,
+}
+
 /// All stability data for all variables.
 pub type StabilityData = HashMap<VariableId, VariableStability>;
 
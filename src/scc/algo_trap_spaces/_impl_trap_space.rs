@@ -0,0 +1,75 @@
+use crate::scc::Behaviour;
+use crate::scc::algo_trap_spaces::TrapSpace;
+use biodivine_lib_param_bn::VariableId;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{
+    GraphColoredVertices, GraphColors, SymbolicAsyncGraph,
+};
+use json::JsonValue;
+use std::collections::HashMap;
+
+impl TrapSpace {
+    /// The fixed variables of this space.
+    pub fn fixed_values(&self) -> Vec<(VariableId, bool)> {
+        self.values
+            .iter()
+            .enumerate()
+            .filter_map(|(i, value)| value.map(|value| (VariableId::from_index(i), value)))
+            .collect()
+    }
+
+    /// Find which of the given attractor components intersect this trap space (and are thus
+    /// contained in it), restricted to the colours of this trap space.
+    ///
+    /// The components are in the format of `Classifier::export_components`. The result
+    /// contains the index of each relevant component and its behaviour classification
+    /// restricted to the colours where the component appears in the space.
+    pub fn contained_attractors(
+        &self,
+        graph: &SymbolicAsyncGraph,
+        components: &[(GraphColoredVertices, HashMap<Behaviour, GraphColors>)],
+    ) -> Vec<(usize, HashMap<Behaviour, GraphColors>)> {
+        let space = graph
+            .mk_subspace(&self.fixed_values())
+            .intersect_colors(&self.colors);
+        components
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (component, behaviour))| {
+                let colors = component.intersect(&space).colors();
+                if colors.is_empty() {
+                    return None;
+                }
+                let behaviour = behaviour
+                    .iter()
+                    .map(|(b, c)| (*b, c.intersect(&colors)))
+                    .filter(|(_, c)| !c.is_empty())
+                    .collect();
+                Some((i, behaviour))
+            })
+            .collect()
+    }
+
+    /// Convert this space to JSON. The space is given both as a string (`0`/`1`/`-` for each
+    /// variable) and as an object with the fixed variables.
+    pub fn to_json(&self, graph: &SymbolicAsyncGraph) -> JsonValue {
+        let mut fixed = JsonValue::new_object();
+        for (var, value) in self.fixed_values() {
+            fixed.insert(&graph.get_variable_name(var), value).unwrap();
+        }
+        let space = self
+            .values
+            .iter()
+            .map(|value| match value {
+                Some(true) => '1',
+                Some(false) => '0',
+                None => '-',
+            })
+            .collect::<String>();
+        object! {
+            "space" => space,
+            "fixed" => fixed,
+            "cardinality" => self.colors.approx_cardinality(),
+        }
+    }
+}
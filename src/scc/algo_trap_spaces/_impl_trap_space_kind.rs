@@ -0,0 +1,26 @@
+use crate::scc::algo_trap_spaces::TrapSpaceKind;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+impl Display for TrapSpaceKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrapSpaceKind::Minimal => write!(f, "minimal"),
+            TrapSpaceKind::Maximal => write!(f, "maximal"),
+            TrapSpaceKind::Essential => write!(f, "essential"),
+        }
+    }
+}
+
+impl TryFrom<&str> for TrapSpaceKind {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "minimal" => Ok(TrapSpaceKind::Minimal),
+            "maximal" => Ok(TrapSpaceKind::Maximal),
+            "essential" => Ok(TrapSpaceKind::Essential),
+            _ => Err(format!("Invalid trap space kind `{}`.", value)),
+        }
+    }
+}
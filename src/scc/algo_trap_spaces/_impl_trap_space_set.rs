@@ -0,0 +1,142 @@
+use crate::scc::algo_trap_spaces::{TrapSpace, TrapSpaceKind, TrapSpaceSet};
+use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+use biodivine_lib_param_bn::trap_spaces::{NetworkColoredSpaces, SymbolicSpaceContext, TrapSpaces};
+use biodivine_lib_param_bn::{BooleanNetwork, ExtendedBoolean, Space};
+
+impl TrapSpaceSet {
+    /// Compute the trap spaces of the given kind for all parametrizations of the network.
+    pub fn compute(network: &BooleanNetwork, kind: TrapSpaceKind) -> Result<TrapSpaceSet, String> {
+        let context = SymbolicSpaceContext::new(network);
+        let graph = SymbolicAsyncGraph::with_space_context(network, &context)?;
+        let unit = context.mk_unit_colored_spaces(&graph);
+        let spaces = match kind {
+            TrapSpaceKind::Minimal => TrapSpaces::minimal_symbolic(&context, &graph, &unit),
+            TrapSpaceKind::Essential => TrapSpaces::essential_symbolic(&context, &graph, &unit),
+            TrapSpaceKind::Maximal => {
+                // The whole state space is always a trap space, so it has to be removed
+                // before maximization to obtain non-trivial results.
+                let essential = TrapSpaces::essential_symbolic(&context, &graph, &unit);
+                let trivial = context.mk_space(&Space::new(network));
+                let non_trivial = essential.as_bdd().and_not(&trivial);
+                let non_trivial = NetworkColoredSpaces::new(non_trivial, &context);
+                TrapSpaces::maximize(&context, &non_trivial)
+            }
+        };
+        Ok(TrapSpaceSet {
+            context,
+            graph,
+            kind,
+            spaces,
+        })
+    }
+
+    pub fn kind(&self) -> TrapSpaceKind {
+        self.kind
+    }
+
+    /// Approximate number of distinct trap spaces (across all parametrizations).
+    pub fn approx_space_count(&self) -> f64 {
+        self.spaces.spaces().approx_cardinality()
+    }
+
+    /// Approximate number of (space, colour) pairs in this set.
+    pub fn approx_cardinality(&self) -> f64 {
+        self.spaces.approx_cardinality()
+    }
+
+    /// Enumerate at most `limit` distinct trap spaces, together with the colours for which
+    /// they appear in this set.
+    ///
+    /// The colours are transferred into the `target` graph (typically the graph used for
+    /// attractor computation).
+    pub fn enumerate(&self, target: &SymbolicAsyncGraph, limit: usize) -> Vec<TrapSpace> {
+        self.spaces
+            .spaces()
+            .iter()
+            .take(limit)
+            .map(|space| {
+                let space_bdd = self.context.mk_space(&space);
+                let colors = self.spaces.as_bdd().and(&space_bdd);
+                let colors = NetworkColoredSpaces::new(colors, &self.context).colors();
+                let colors = target
+                    .transfer_colors_from(&colors, &self.graph)
+                    .expect("Trap space graph is not compatible with the target graph.");
+                let values = self
+                    .graph
+                    .variables()
+                    .map(|var| match space[var] {
+                        ExtendedBoolean::Zero => Some(false),
+                        ExtendedBoolean::One => Some(true),
+                        ExtendedBoolean::Any => None,
+                    })
+                    .collect();
+                TrapSpace { values, colors }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scc::algo_trap_spaces::{TrapSpaceKind, TrapSpaceSet};
+    use biodivine_lib_param_bn::BooleanNetwork;
+    use biodivine_lib_param_bn::biodivine_std::traits::Set;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use std::convert::TryFrom;
+
+    // Two independent switches: every half of the state space is a trap space, hence
+    // the maximal trap spaces fix one variable while the minimal ones fix both.
+    const MODEL: &str = "A -> A\nB -> B\n$A: A\n$B: B\n";
+
+    fn sorted_values(set: &TrapSpaceSet, graph: &SymbolicAsyncGraph) -> Vec<Vec<Option<bool>>> {
+        let mut values = set
+            .enumerate(graph, 10)
+            .into_iter()
+            .inspect(|space| assert!(!space.colors.is_empty()))
+            .map(|space| space.values)
+            .collect::<Vec<_>>();
+        values.sort();
+        values
+    }
+
+    #[test]
+    fn minimal_trap_spaces() {
+        let network = BooleanNetwork::try_from(MODEL).unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let set = TrapSpaceSet::compute(&network, TrapSpaceKind::Minimal).unwrap();
+        assert_eq!(TrapSpaceKind::Minimal, set.kind());
+        assert_eq!(4.0, set.approx_space_count());
+        assert_eq!(
+            vec![
+                vec![Some(false), Some(false)],
+                vec![Some(false), Some(true)],
+                vec![Some(true), Some(false)],
+                vec![Some(true), Some(true)],
+            ],
+            sorted_values(&set, &graph)
+        );
+    }
+
+    #[test]
+    fn maximal_trap_spaces() {
+        let network = BooleanNetwork::try_from(MODEL).unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let set = TrapSpaceSet::compute(&network, TrapSpaceKind::Maximal).unwrap();
+        assert_eq!(TrapSpaceKind::Maximal, set.kind());
+        assert_eq!(4.0, set.approx_space_count());
+        // The whole state space is a trap space as well, but it is never reported.
+        assert_eq!(
+            vec![
+                vec![None, Some(false)],
+                vec![None, Some(true)],
+                vec![Some(false), None],
+                vec![Some(true), None],
+            ],
+            sorted_values(&set, &graph)
+        );
+        for space in set.enumerate(&graph, 10) {
+            assert_eq!(1, space.fixed_values().len());
+        }
+        assert_eq!(1, set.enumerate(&graph, 1).len());
+    }
+}
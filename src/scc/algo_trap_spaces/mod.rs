@@ -0,0 +1,39 @@
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+use biodivine_lib_param_bn::trap_spaces::{NetworkColoredSpaces, SymbolicSpaceContext};
+
+mod _impl_trap_space;
+mod _impl_trap_space_kind;
+mod _impl_trap_space_set;
+
+/// Which trap spaces should be computed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TrapSpaceKind {
+    /// Inclusion-minimal trap spaces (each contains at least one attractor).
+    Minimal,
+    /// Inclusion-maximal trap spaces (excluding the whole state space).
+    Maximal,
+    /// Trap spaces where all fixed variables are essential (i.e. not implied by percolation).
+    Essential,
+}
+
+/// Trap spaces of a parametrized network, computed symbolically for all parametrizations.
+///
+/// The spaces are represented using a dedicated `SymbolicSpaceContext` and graph, i.e. the
+/// colours of `spaces` are not compatible with a graph created using `SymbolicAsyncGraph::new`.
+/// Use `TrapSpaceSet::enumerate` to obtain colours that are valid in another graph.
+pub struct TrapSpaceSet {
+    context: SymbolicSpaceContext,
+    graph: SymbolicAsyncGraph,
+    kind: TrapSpaceKind,
+    spaces: NetworkColoredSpaces,
+}
+
+/// One trap space, together with the parametrizations in which it is a trap space
+/// (of the requested kind).
+#[derive(Clone, Debug)]
+pub struct TrapSpace {
+    /// Value of each network variable in the space (`None` if free).
+    pub values: Vec<Option<bool>>,
+    /// Colours of the original (attractor) graph for which this is a trap space.
+    pub colors: GraphColors,
+}
@@ -1,16 +1,36 @@
+use biodivine_lib_param_bn::VariableId;
+use biodivine_lib_param_bn::biodivine_std::bitvector::ArrayBitVector;
 use biodivine_lib_param_bn::symbolic_async_graph::{GraphColoredVertices, GraphColors};
 use num_bigint::BigUint;
 use std::collections::HashMap;
 use std::sync::Mutex;
 use std::sync::atomic::AtomicUsize;
 
+/// **(internal)** Computation of explicit attractor state graphs.
+mod _impl_attractor_graph;
+/// **(internal)** Computation of compact summaries of (large) attractors.
+mod _impl_attractor_summary;
 mod _impl_behaviour;
 /// **(internal)** Utility methods for the behavior `Class`.
 mod _impl_class;
 /// **(internal)** Implementation of `Behaviour` classification in `Classifier`.
 mod _impl_classifier;
 mod _impl_progress_tracker;
+/// Attractor detection using ITGR reduction and the Xie-Beerel algorithm.
+pub mod algo_attractor_search;
+/// Weak and strong basins of attraction.
+pub mod algo_basin_analysis;
+/// Direct symbolic computation of fixed points (sink states).
+pub mod algo_fixed_points;
+/// Enumeration of coloured state sets projected onto selected variables.
+pub mod algo_projection;
+/// Symbolic forward/backward reachability.
+pub mod algo_reachability;
 pub mod algo_stability_analysis;
+/// Symbolic (minimal, maximal, essential) trap spaces of parametrized networks.
+pub mod algo_trap_spaces;
+
+pub use _impl_attractor_graph::{attractors_to_dot, state_to_json};
 
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Behaviour {
@@ -32,3 +52,70 @@ pub struct ProgressTracker {
     remaining: Mutex<BigUint>,
     results: AtomicUsize,
 }
+
+/// The state graph of one attractor of a single (witness) parametrization.
+///
+/// Small attractors are stored explicitly (states and labelled edges). For large attractors,
+/// the explicit graph is omitted and only a `summary` is provided.
+#[derive(Clone, Debug)]
+pub struct AttractorGraph {
+    pub behaviour: Behaviour,
+    /// Value of each network variable in the enclosing subspace (`None` if not fixed).
+    pub subspace: Vec<Option<bool>>,
+    /// Exact number of attractor states.
+    pub state_count: BigUint,
+    /// Explicit attractor states (empty for large attractors).
+    pub states: Vec<ArrayBitVector>,
+    /// Explicit attractor transitions (empty for large attractors).
+    pub edges: Vec<AttractorEdge>,
+    /// A summary of the attractor, present only if the attractor is too large to be explicit.
+    pub summary: Option<AttractorSummary>,
+}
+
+/// A transition between two states of an `AttractorGraph` (identified by their index),
+/// labelled with the variable which is updated by the transition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AttractorEdge {
+    pub source: usize,
+    pub variable: VariableId,
+    pub target: usize,
+}
+
+/// A compact description of a (typically large) attractor of a single parametrization.
+///
+/// Instead of listing all states, the summary stores the smallest subspace enclosing the
+/// attractor, the number of states, a sample of representative states and transitions,
+/// and optionally an SCC quotient of the attractor with respect to a few variables.
+#[derive(Clone, Debug)]
+pub struct AttractorSummary {
+    /// Value of each network variable in the enclosing subspace (`None` if not fixed).
+    pub subspace: Vec<Option<bool>>,
+    /// Exact number of attractor states.
+    pub state_count: BigUint,
+    /// A sample of attractor states.
+    pub sampled_states: Vec<ArrayBitVector>,
+    /// Transitions `(source, updated variable, target)` outgoing from the sampled states.
+    pub sampled_transitions: Vec<(ArrayBitVector, VariableId, ArrayBitVector)>,
+    /// The SCC quotient of the attractor with respect to selected variables (if any).
+    pub quotient: Option<AttractorQuotient>,
+}
+
+/// The maximal number of variables that can be used to build an `AttractorQuotient`.
+pub const MAX_QUOTIENT_VARIABLES: usize = 8;
+
+/// The maximal number of nodes (components) of an `AttractorQuotient`.
+pub const MAX_QUOTIENT_NODES: usize = 1024;
+
+/// An SCC quotient of an attractor state graph: states with the same projection onto the
+/// selected `variables` are split into strongly connected components (using transitions
+/// of the remaining variables), and two components are connected if some transition
+/// (updating one of the selected variables) leads from one to the other.
+#[derive(Clone, Debug)]
+pub struct AttractorQuotient {
+    pub variables: Vec<VariableId>,
+    /// Components as projected valuations (in the order of `variables`) with their exact
+    /// state counts. One valuation can appear in multiple components.
+    pub nodes: Vec<(Vec<bool>, BigUint)>,
+    /// Edges between nodes (indices into `nodes`), labelled by the updated variable.
+    pub edges: Vec<(usize, VariableId, usize)>,
+}
@@ -0,0 +1,175 @@
+use biodivine_lib_bdd::Bdd;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+use biodivine_lib_param_bn::{BinaryOp, BooleanNetwork, FnUpdate, VariableId};
+use std::collections::HashSet;
+
+/// The maximal number of distinct instantiations of one update function that we examine
+/// when looking for the simplest one. Beyond this, we just keep the best one found so far.
+const MAX_FUNCTION_CANDIDATES: usize = 256;
+
+/// Pick a witness network from the given colour set whose update functions are as simple
+/// as possible, and write these update functions in a minimized DNF form.
+///
+/// The colours are fixed one variable at a time: for each variable, we examine the distinct
+/// instantiations of its update function that are still admissible and restrict the colours
+/// to the one with the fewest literals (preferring monotone functions when tied). This is
+/// a greedy process, so the result is not guaranteed to be globally minimal.
+///
+/// The `network` must be the network from which the `graph` was created. Returns an error
+/// if a minimized update function cannot be used in the witness network.
+pub fn pick_minimal_witness(
+    graph: &SymbolicAsyncGraph,
+    network: &BooleanNetwork,
+    colors: &GraphColors,
+) -> Result<BooleanNetwork, String> {
+    let context = graph.symbolic_context();
+    let mut remaining = colors.clone();
+    for var in network.variables() {
+        let fn_is_true = update_function_is_true(graph, network, var);
+
+        let mut candidates = remaining.clone();
+        let mut best: Option<((usize, usize), GraphColors)> = None;
+        let mut explored = 0;
+        while !candidates.is_empty() && explored < MAX_FUNCTION_CANDIDATES {
+            let instantiated =
+                instantiate_function(graph, &fn_is_true, &candidates.pick_singleton());
+            // All colours in which the update function has this exact instantiation.
+            let same_function = fn_is_true
+                .iff(&instantiated)
+                .for_all(context.state_variables());
+            let same_function = candidates.copy(candidates.as_bdd().and(&same_function));
+            let complexity = function_complexity(graph, &instantiated);
+            if best.as_ref().map(|(c, _)| complexity < *c).unwrap_or(true) {
+                best = Some((complexity, same_function.clone()));
+            }
+            candidates = candidates.minus(&same_function);
+            explored += 1;
+        }
+
+        if let Some((_, best_colors)) = best {
+            remaining = best_colors;
+        }
+    }
+
+    let witness_colour = remaining.pick_singleton();
+    let mut witness = graph.pick_witness(&witness_colour);
+    for var in network.variables() {
+        let fn_is_true = update_function_is_true(graph, network, var);
+        let instantiated = instantiate_function(graph, &fn_is_true, &witness_colour);
+        let function = minimized_function(graph, &instantiated);
+        witness.set_update_function(var, Some(function))?;
+    }
+    Ok(witness)
+}
+
+/// **(internal)** The BDD (over states and parameters) of the inputs for which the update
+/// function of `var` is true. Works for both explicit and implicit update functions.
+fn update_function_is_true(
+    graph: &SymbolicAsyncGraph,
+    network: &BooleanNetwork,
+    var: VariableId,
+) -> Bdd {
+    let context = graph.symbolic_context();
+    if let Some(function) = network.get_update_function(var) {
+        context.mk_fn_update_true(function)
+    } else {
+        context.mk_implicit_function_is_true(var, &network.regulators(var))
+    }
+}
+
+/// **(internal)** Compute the state-space BDD of an update function (given as a BDD over
+/// states and parameters) for one fixed witness colour.
+fn instantiate_function(graph: &SymbolicAsyncGraph, function: &Bdd, colour: &GraphColors) -> Bdd {
+    function
+        .and(colour.as_bdd())
+        .exists(graph.symbolic_context().parameter_variables())
+}
+
+/// **(internal)** The complexity of an instantiated update function: the number of literals in
+/// its minimized DNF, followed by the number of variables that appear in both polarities.
+fn function_complexity(graph: &SymbolicAsyncGraph, function: &Bdd) -> (usize, usize) {
+    let mut literals = 0;
+    let mut positive = HashSet::new();
+    let mut negative = HashSet::new();
+    for clause in function.to_optimized_dnf() {
+        for var in graph.symbolic_context().state_variables() {
+            match clause.get_value(*var) {
+                Some(true) => {
+                    literals += 1;
+                    positive.insert(*var);
+                }
+                Some(false) => {
+                    literals += 1;
+                    negative.insert(*var);
+                }
+                None => (),
+            }
+        }
+    }
+    (literals, positive.intersection(&negative).count())
+}
+
+/// **(internal)** Convert an instantiated update function into a minimized DNF `FnUpdate`.
+fn minimized_function(graph: &SymbolicAsyncGraph, function: &Bdd) -> FnUpdate {
+    let state_variables = graph
+        .variables()
+        .zip(graph.symbolic_context().state_variables().iter().cloned())
+        .collect::<Vec<(VariableId, _)>>();
+    let clauses = function
+        .to_optimized_dnf()
+        .into_iter()
+        .map(|clause| {
+            let literals = state_variables
+                .iter()
+                .filter_map(|(var, bdd_var)| {
+                    clause.get_value(*bdd_var).map(|value| {
+                        if value {
+                            FnUpdate::Var(*var)
+                        } else {
+                            FnUpdate::Not(Box::new(FnUpdate::Var(*var)))
+                        }
+                    })
+                })
+                .collect::<Vec<_>>();
+            fold_binary(BinaryOp::And, literals, true)
+        })
+        .collect::<Vec<_>>();
+    fold_binary(BinaryOp::Or, clauses, false)
+}
+
+/// **(internal)** Fold the given list of functions using a binary operator. An empty list
+/// is converted to the given default constant.
+fn fold_binary(op: BinaryOp, functions: Vec<FnUpdate>, default: bool) -> FnUpdate {
+    functions
+        .into_iter()
+        .reduce(|left, right| FnUpdate::Binary(op, Box::new(left), Box::new(right)))
+        .unwrap_or(FnUpdate::Const(default))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::witness::pick_minimal_witness;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use biodivine_lib_param_bn::{BooleanNetwork, FnUpdate};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn minimal_witness_functions() {
+        // `C` can be any monotone function of `A` and `B` (including constants).
+        let network =
+            BooleanNetwork::try_from("A -| B\nA ->? C\nB ->? C\n$A: true\n$B: !A\n$C: g(A, B)\n")
+                .unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let c = network.as_graph().find_variable("C").unwrap();
+
+        let witness = pick_minimal_witness(&graph, &network, &graph.mk_unit_colors()).unwrap();
+        assert!(matches!(
+            witness.get_update_function(c),
+            Some(FnUpdate::Const(_))
+        ));
+        // The fixed update functions are kept (up to their minimized form).
+        let a = network.as_graph().find_variable("A").unwrap();
+        assert_eq!(witness.get_update_function(a), &Some(FnUpdate::Const(true)));
+    }
+}
@@ -0,0 +1,169 @@
+use crate::witness::{WitnessSampler, WitnessSelection};
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+use std::convert::TryFrom;
+
+impl TryFrom<(&str, Option<u64>)> for WitnessSelection {
+    type Error = String;
+
+    fn try_from((mode, seed): (&str, Option<u64>)) -> Result<Self, Self::Error> {
+        match mode {
+            "first" => Ok(WitnessSelection::First),
+            "random" => Ok(WitnessSelection::Random {
+                seed: seed.unwrap_or_default(),
+            }),
+            "diverse" => Ok(WitnessSelection::Diverse),
+            _ => Err(format!("Invalid witness selection mode `{}`.", mode)),
+        }
+    }
+}
+
+impl<'a> WitnessSampler<'a> {
+    /// Create a new sampler which enumerates distinct witness colours of the given set.
+    pub fn new(
+        graph: &'a SymbolicAsyncGraph,
+        colors: &GraphColors,
+        selection: WitnessSelection,
+    ) -> WitnessSampler<'a> {
+        let rng_state = match selection {
+            WitnessSelection::Random { seed } => seed,
+            _ => 0,
+        };
+        WitnessSampler {
+            graph,
+            remaining: colors.clone(),
+            selection,
+            picked: Vec::new(),
+            rng_state,
+        }
+    }
+
+    /// **(internal)** Pick one colour uniformly at random from the remaining colours.
+    ///
+    /// The value of each parameter variable is chosen with a probability proportional
+    /// to the number of colours that remain admissible after the choice.
+    fn pick_random(&mut self) -> GraphColors {
+        let mut current = self.remaining.clone();
+        for var in self.graph.symbolic_context().parameter_variables() {
+            let when_true = current.copy(current.as_bdd().var_select(*var, true));
+            let when_false = current.copy(current.as_bdd().var_select(*var, false));
+            let true_count = when_true.approx_cardinality();
+            let false_count = when_false.approx_cardinality();
+            let threshold = true_count / (true_count + false_count);
+            current = if self.next_random() < threshold {
+                when_true
+            } else {
+                when_false
+            };
+        }
+        current
+    }
+
+    /// **(internal)** Pick one colour which differs from the already picked colours in as
+    /// many parameter variables as possible.
+    ///
+    /// This is a greedy heuristic: for each parameter variable, we prefer the value that
+    /// is less common among the picked witnesses, as long as some colour remains admissible.
+    fn pick_diverse(&self) -> GraphColors {
+        let mut current = self.remaining.clone();
+        for var in self.graph.symbolic_context().parameter_variables() {
+            let picked_true = self
+                .picked
+                .iter()
+                .filter(|it| !it.as_bdd().var_select(*var, true).is_false())
+                .count();
+            let prefer = 2 * picked_true < self.picked.len();
+            let preferred = current.copy(current.as_bdd().var_select(*var, prefer));
+            current = if preferred.is_empty() {
+                current.copy(current.as_bdd().var_select(*var, !prefer))
+            } else {
+                preferred
+            };
+        }
+        current.pick_singleton()
+    }
+
+    /// **(internal)** A simple SplitMix64 generator producing numbers from `[0,1)`.
+    ///
+    /// We do not need cryptographic quality here, just a reproducible sequence for each seed.
+    fn next_random(&mut self) -> f64 {
+        self.rng_state = self.rng_state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.rng_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl Iterator for WitnessSampler<'_> {
+    type Item = GraphColors;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        let witness = match self.selection {
+            WitnessSelection::First => self.remaining.pick_singleton(),
+            WitnessSelection::Random { .. } => self.pick_random(),
+            WitnessSelection::Diverse => self.pick_diverse(),
+        };
+        self.remaining = self.remaining.minus(&witness);
+        self.picked.push(witness.clone());
+        Some(witness)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::witness::{WitnessSampler, WitnessSelection};
+    use biodivine_lib_param_bn::BooleanNetwork;
+    use biodivine_lib_param_bn::biodivine_std::traits::Set;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn witness_sampling_modes() {
+        let network = BooleanNetwork::try_from("$A: p\n$B: q\n").unwrap();
+        let graph = SymbolicAsyncGraph::new(&network).unwrap();
+        let unit = graph.mk_unit_colors();
+
+        for mode in ["first", "random", "diverse"] {
+            let selection = WitnessSelection::try_from((mode, Some(7))).unwrap();
+            let witnesses = WitnessSampler::new(&graph, &unit, selection).collect::<Vec<_>>();
+            // All four colours are enumerated, each exactly once.
+            assert_eq!(witnesses.len(), 4);
+            let mut covered = graph.mk_empty_colors();
+            for witness in &witnesses {
+                assert_eq!(witness.approx_cardinality(), 1.0);
+                assert!(witness.intersect(&covered).is_empty());
+                covered = covered.union(witness);
+            }
+            assert_eq!(covered, unit);
+        }
+
+        // The same seed gives the same witnesses.
+        let random = |seed| {
+            let selection = WitnessSelection::Random { seed };
+            WitnessSampler::new(&graph, &unit, selection).collect::<Vec<_>>()
+        };
+        assert_eq!(random(3), random(3));
+
+        // The second diverse witness differs from the first one in both parameters.
+        let diverse =
+            WitnessSampler::new(&graph, &unit, WitnessSelection::Diverse).collect::<Vec<_>>();
+        for name in ["p", "q"] {
+            let parameter = network.find_parameter(name).unwrap();
+            let is_true = graph
+                .symbolic_context()
+                .mk_uninterpreted_function_is_true(parameter, &[]);
+            let is_true = unit.copy(unit.as_bdd().and(&is_true));
+            assert_ne!(
+                diverse[0].is_subset(&is_true),
+                diverse[1].is_subset(&is_true)
+            );
+        }
+
+        assert!(WitnessSelection::try_from(("best", None)).is_err());
+    }
+}
@@ -0,0 +1,30 @@
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+
+/// **(internal)** Selection of witnesses with the simplest update functions.
+mod _impl_minimal_witness;
+/// **(internal)** Implementation of the witness colour enumeration.
+mod _impl_witness_sampler;
+
+pub use _impl_minimal_witness::pick_minimal_witness;
+
+/// Describes how distinct witness colours are selected from a colour set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WitnessSelection {
+    /// Pick witnesses deterministically, in the order given by the BDD.
+    First,
+    /// Pick witnesses uniformly at random, using the given seed.
+    Random { seed: u64 },
+    /// Greedily pick witnesses that differ from the already selected witnesses
+    /// in as many function table rows (parameter valuations) as possible.
+    Diverse,
+}
+
+/// Enumerates distinct singleton colours (i.e. fully instantiated parametrizations) from
+/// a colour set, one at a time, using a particular `WitnessSelection` strategy.
+pub struct WitnessSampler<'a> {
+    graph: &'a SymbolicAsyncGraph,
+    remaining: GraphColors,
+    selection: WitnessSelection,
+    picked: Vec<GraphColors>,
+    rng_state: u64,
+}